@@ -0,0 +1,149 @@
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+
+use crate::resources::run::GameMode;
+
+pub const USAGE: &str = "\
+Usage: bubble_collapse [options]
+
+  --seed <n>              seed the first run
+  --mode <mode>           endless or time-attack
+  --start-in-game         skip the main menu
+  --windowed              run in a window (the default)
+  --fullscreen            run borderless fullscreen
+  --resolution <w>x<h>    window size, e.g. 1920x1080
+  --replay <file>         play back a recorded run
+  --headless              run without a window, straight into a run
+  --ticks <n>             quit after this many frames
+  --benchmark             run the stress benchmark headless
+  --help                  show this message";
+
+#[derive(Resource, Debug, Default, Clone, PartialEq)]
+pub struct LaunchOptions {
+    pub seed: Option<u64>,
+    pub mode: Option<GameMode>,
+    pub start_in_game: bool,
+    pub fullscreen: bool,
+    pub resolution: Option<Vec2>,
+    pub replay: Option<PathBuf>,
+    pub headless: bool,
+    pub ticks: Option<u32>,
+    pub benchmark: bool,
+    pub help: bool,
+}
+
+impl LaunchOptions {
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut options = Self::default();
+        let mut windowed = false;
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--seed" => options.seed = Some(parse_number(&arg, args.next())?),
+                "--mode" => {
+                    let value = value_for(&arg, args.next())?;
+                    let mode = GameMode::from_id(&value).ok_or_else(|| format!("unknown mode `{}`", value))?;
+                    options.mode = Some(mode);
+                }
+                "--start-in-game" => options.start_in_game = true,
+                "--windowed" => windowed = true,
+                "--fullscreen" => options.fullscreen = true,
+                "--resolution" => options.resolution = Some(parse_resolution(&value_for(&arg, args.next())?)?),
+                "--replay" => options.replay = Some(PathBuf::from(value_for(&arg, args.next())?)),
+                "--headless" => options.headless = true,
+                "--ticks" => options.ticks = Some(parse_number(&arg, args.next())?),
+                "--benchmark" => options.benchmark = true,
+                "--help" | "-h" => options.help = true,
+                _ => return Err(format!("unknown argument `{}`", arg)),
+            }
+        }
+        if windowed && options.fullscreen {
+            return Err("`--windowed` and `--fullscreen` can't be used together".to_string());
+        }
+        Ok(options)
+    }
+
+    // Nothing can click through the menu without a window, and replays and
+    // benchmarks have their run lined up already
+    pub fn skips_menu(&self) -> bool {
+        self.start_in_game || self.headless || self.benchmark || self.replay.is_some()
+    }
+
+    pub fn runs_headless(&self) -> bool {
+        self.headless || self.benchmark
+    }
+}
+
+fn value_for(arg: &str, value: Option<String>) -> Result<String, String> {
+    value.ok_or_else(|| format!("`{}` needs a value", arg))
+}
+
+fn parse_number<T: std::str::FromStr>(arg: &str, value: Option<String>) -> Result<T, String> {
+    let value = value_for(arg, value)?;
+    value.parse().map_err(|_| format!("`{}` expects a number, got `{}`", arg, value))
+}
+
+fn parse_resolution(value: &str) -> Result<Vec2, String> {
+    let invalid = || format!("`--resolution` expects a size like 1280x720, got `{}`", value);
+    let (width, height) = value.split_once('x').ok_or_else(invalid)?;
+    let width = width.parse::<u32>().map_err(|_| invalid())?;
+    let height = height.parse::<u32>().map_err(|_| invalid())?;
+    if width == 0 || height == 0 {
+        return Err(invalid());
+    }
+    Ok(Vec2::new(width as f32, height as f32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<LaunchOptions, String> {
+        LaunchOptions::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn parses_options() {
+        let options = parse(&["--seed", "7", "--mode", "time-attack", "--headless", "--ticks", "600"]).unwrap();
+        assert_eq!(options.seed, Some(7));
+        assert_eq!(options.mode, Some(GameMode::TimeAttack));
+        assert!(options.headless);
+        assert_eq!(options.ticks, Some(600));
+    }
+
+    #[test]
+    fn rejects_missing_value() {
+        assert_eq!(parse(&["--seed"]), Err("`--seed` needs a value".to_string()));
+        assert_eq!(parse(&["--resolution"]), Err("`--resolution` needs a value".to_string()));
+    }
+
+    #[test]
+    fn rejects_bad_number() {
+        assert_eq!(parse(&["--ticks", "ten"]), Err("`--ticks` expects a number, got `ten`".to_string()));
+        assert!(parse(&["--seed", "-1"]).is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_argument() {
+        assert_eq!(parse(&["--fast"]), Err("unknown argument `--fast`".to_string()));
+        assert!(parse(&["--mode", "zen"]).is_err());
+    }
+
+    #[test]
+    fn rejects_windowed_and_fullscreen() {
+        let error = Err("`--windowed` and `--fullscreen` can't be used together".to_string());
+        assert_eq!(parse(&["--fullscreen", "--windowed"]), error);
+        assert_eq!(parse(&["--windowed", "--fullscreen"]), error);
+        assert!(!parse(&["--windowed"]).unwrap().fullscreen);
+    }
+
+    #[test]
+    fn parses_resolution() {
+        assert_eq!(parse_resolution("1280x720"), Ok(Vec2::new(1280.0, 720.0)));
+        assert!(parse_resolution("0x720").is_err());
+        assert!(parse_resolution("1280x0").is_err());
+        assert!(parse_resolution("1280").is_err());
+        assert!(parse_resolution("widex720").is_err());
+    }
+}
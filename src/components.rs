@@ -2,3 +2,9 @@ pub mod bubbles;
 pub mod physics;
 pub mod ui;
 pub mod audio;
+pub mod popups;
+pub mod particles;
+pub mod camera;
+pub mod interaction;
+pub mod pop_tools;
+pub mod music;
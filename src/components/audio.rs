@@ -1,4 +1,23 @@
-use bevy::prelude::*;
+use bevy::{audio::Volume, prelude::*};
+
+use crate::resources::audio::AudioCategory;
 
 #[derive(Component, Debug, Clone)]
 pub struct AudioLimitRemovalFlag;
+
+// Which category a sound belongs to, and the volume it was asked to play at
+// before the category's volume gets applied
+#[derive(Component, Debug, Clone, Copy)]
+pub struct AudioBus {
+    pub category: AudioCategory,
+    pub volume: f32,
+}
+
+impl AudioBus {
+    pub fn new(category: AudioCategory, volume: Volume) -> Self {
+        Self {
+            category,
+            volume: volume.get(),
+        }
+    }
+}
@@ -1,10 +1,11 @@
 use std::time::Duration;
 
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use crate::{components::physics::{Collider, Velocity}, resources::cache::MeshCache, util::ActionTimer};
 
-#[derive(Component, Default, Debug)]
+#[derive(Component, Default, Debug, Clone, Serialize, Deserialize)]
 pub struct Bubble {
     pub radius: f32,
     pub initial_radius: f32,
@@ -12,6 +13,7 @@ pub struct Bubble {
     pub bubble_type: BubbleType,
     pub collapse_timer: Timer,
     pub max_y_velocity: f32,
+    pub popped_by_user: bool,
 }
 
 impl Bubble {
@@ -23,6 +25,7 @@ impl Bubble {
             bubble_type,
             collapse_timer: Timer::from_seconds(collapse_time, TimerMode::Once),
             max_y_velocity,
+            popped_by_user: false,
         }
     }
 
@@ -41,14 +44,14 @@ impl Bubble {
     }
 }
 
-#[derive(Default, Debug, PartialEq)]
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum BubbleState {
     #[default]
     Moving,
     Popped,
 }
 
-#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum BubbleType {
     #[default]
     Normal,
@@ -58,6 +61,22 @@ pub enum BubbleType {
     BlackHole,
 }
 
+impl BubbleType {
+    pub fn all() -> [BubbleType; 5] {
+        [BubbleType::Normal, BubbleType::Mega, BubbleType::ScatterShot, BubbleType::Beam, BubbleType::BlackHole]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            BubbleType::Normal => "Normal",
+            BubbleType::Mega => "Mega",
+            BubbleType::ScatterShot => "Scatter Shot",
+            BubbleType::Beam => "Beam",
+            BubbleType::BlackHole => "Black Hole",
+        }
+    }
+}
+
 #[derive(Bundle)]
 pub struct BubbleBundle {
     pub mesh: Mesh2d,
@@ -120,7 +139,7 @@ impl BubbleBundle {
     }
 }
 
-#[derive(Component, Default, Debug, Clone)]
+#[derive(Component, Default, Debug, Clone, Serialize, Deserialize)]
 pub struct TimedEffect {
     pub timer: Timer,
 }
@@ -161,19 +180,23 @@ impl TimedEffect {
     }
 }
 
-#[derive(Component, Default, Debug, Clone)]
+#[derive(Component, Default, Debug, Clone, Serialize, Deserialize)]
 pub struct BubbleShockwave {
     initial_radius: f32,
     pub radius: f32,
     pub max_radius: f32,
+    // the kind of bubble whose pop set this shockwave off
+    #[serde(default)]
+    pub source: BubbleType,
 }
 
 impl BubbleShockwave {
-    pub fn new(radius: f32, max_radius: f32) -> Self {
+    pub fn new(radius: f32, max_radius: f32, source: BubbleType) -> Self {
         Self {
             initial_radius: radius,
             radius,
             max_radius,
+            source,
         }
     }
 
@@ -192,7 +215,7 @@ pub struct BubbleShockwaveBundle {
     pub collider: Collider,
 }
 
-#[derive(Component, Default, Debug)]
+#[derive(Component, Default, Debug, Clone, Serialize, Deserialize)]
 pub struct BubbleBlackHole {
     pub max_radius: f32,
     pub radius: f32,
@@ -227,7 +250,7 @@ pub struct BubbleBlackHoleBundle {
     pub collider: Collider,
 }
 
-#[derive(Component)]
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
 pub struct BubbleScatterShotSpawner {
     pub radius: f32,
     pub variation: f32,
@@ -261,7 +284,7 @@ pub struct BubbleScatterShotSpawnerBundle {
     pub transform: Transform,
 }
 
-#[derive(Component, Debug, Default)]
+#[derive(Component, Debug, Default, Clone, Serialize, Deserialize)]
 pub struct BubbleBeam {
     pub width: f32,
     pub max_width: f32,
@@ -0,0 +1,11 @@
+use bevy::prelude::*;
+
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct MainCamera;
+
+// Where the camera rests when no effects are playing
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct CameraEffectsAnchor {
+    pub translation: Vec3,
+    pub scale: f32,
+}
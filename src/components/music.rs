@@ -0,0 +1,25 @@
+use bevy::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MusicRole {
+    Menu,
+    PlayBase,
+    // Extra layers on top of the play track, louder ones come in first
+    PlayIntensity(u8),
+}
+
+#[derive(Component, Debug, Clone)]
+pub struct MusicTrack {
+    pub role: MusicRole,
+    // 0 is silent, 1 is the track's full volume, before ducking
+    pub fade: f32,
+}
+
+impl MusicTrack {
+    pub fn new(role: MusicRole) -> Self {
+        Self {
+            role,
+            fade: 0.0,
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum ParticleKind {
+    #[default]
+    Droplet,
+    Spark,
+    Debris {
+        center: Vec2,
+        angle: f32,
+        distance: f32,
+        angular_speed: f32,
+    },
+}
+
+#[derive(Component, Debug, Default, Clone)]
+pub struct Particle {
+    pub active: bool,
+    pub kind: ParticleKind,
+    pub velocity: Vec2,
+    pub size: f32,
+    pub lifetime: Timer,
+}
+
+impl Particle {
+    pub fn activate(&mut self, kind: ParticleKind, velocity: Vec2, size: f32, lifetime: Duration) {
+        self.active = true;
+        self.kind = kind;
+        self.velocity = velocity;
+        self.size = size;
+        self.lifetime = Timer::new(lifetime, TimerMode::Once);
+    }
+
+    pub fn deactivate(&mut self) {
+        self.active = false;
+    }
+
+    pub fn progress(&self) -> f32 {
+        self.lifetime.elapsed_secs() / self.lifetime.duration().as_secs_f32()
+    }
+}
@@ -1,6 +1,7 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Component, Debug, Default, Clone)]
+#[derive(Component, Debug, Default, Clone, Serialize, Deserialize)]
 pub struct Collider {
     pub radius: f32,
 }
@@ -9,6 +10,10 @@ impl Collider {
     pub fn is_point_inside(&self, pos: Vec2, point: Vec2) -> bool {
         pos.distance_squared(point) < self.radius * self.radius
     }
+
+    pub fn is_circle_overlapping(&self, pos: Vec2, point: Vec2, radius: f32) -> bool {
+        pos.distance_squared(point) < (self.radius + radius).powi(2)
+    }
 }
 
 #[derive(Component, Debug, Default)]
@@ -0,0 +1,4 @@
+use bevy::prelude::*;
+
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct PopToolIndicator;
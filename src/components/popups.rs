@@ -0,0 +1,43 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+#[derive(Component, Debug, Default, Clone)]
+pub struct ScorePopup {
+    pub active: bool,
+    pub count: u32,
+    pub origin: Vec2,
+    pub lifetime: Timer,
+}
+
+impl ScorePopup {
+    pub fn activate(&mut self, origin: Vec2, amount: u32, lifetime: Duration) {
+        self.active = true;
+        self.count = amount;
+        self.origin = origin;
+        self.lifetime = Timer::new(lifetime, TimerMode::Once);
+    }
+
+    // Merging keeps the popup alive for as long as the chain keeps feeding it
+    pub fn add(&mut self, amount: u32) {
+        self.count += amount;
+        self.lifetime.reset();
+    }
+
+    pub fn deactivate(&mut self) {
+        self.active = false;
+        self.count = 0;
+    }
+
+    pub fn progress(&self) -> f32 {
+        self.lifetime.elapsed_secs() / self.lifetime.duration().as_secs_f32()
+    }
+
+    pub fn label(&self, chain_threshold: u32) -> String {
+        if self.count >= chain_threshold {
+            format!("x{} chain!", self.count)
+        } else {
+            format!("+{}", self.count)
+        }
+    }
+}
@@ -9,6 +9,9 @@ pub struct PauseMenuRoot;
 #[derive(Component, Debug, Default, Clone, Copy)]
 pub struct UpgradesMenuRoot;
 
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct UpgradesMenuButtons;
+
 #[derive(Component, Debug, Clone)]
 pub struct ScoreText {
     pub scale_timer: Timer,
@@ -4,7 +4,11 @@ use bevy::prelude::*;
 pub enum GameState {
     #[default]
     MainMenu,
+    Options,
+    Achievements,
+    Leaderboard,
     InGame,
+    Summary,
 }
 
 #[derive(States, Default, Debug, Clone, PartialEq, Eq, Hash)]
@@ -4,3 +4,5 @@ pub mod systems;
 pub mod plugins;
 pub mod util;
 pub mod game_states;
+pub mod persistence;
+pub mod cli;
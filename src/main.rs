@@ -1,52 +1,84 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use bevy::prelude::*;
-use bevy::winit::WinitWindows;
-use bevy_egui::EguiPlugin;
-use bevy_rand::prelude::EntropyPlugin;
-use bevy_rand::prelude::WyRand;
+use std::time::Duration;
 
+use bevy::app::ScheduleRunnerPlugin;
+use bevy::prelude::*;
+use bevy::render::{settings::{RenderCreation, WgpuSettings}, RenderPlugin};
+use bevy::window::{ExitCondition, MonitorSelection, WindowMode, WindowResolution};
+use bevy::winit::{WinitPlugin, WinitWindows};
 use bevy_framepace::FramepacePlugin;
 
-use bubble_collapse::plugins::camera::CameraPlugin;
-use bubble_collapse::plugins::{
-    game_states::GameStatesPlugin,
-    stats::StatsPlugin,
-    bubbles::BubblesPlugin,
-    physics::PhysicsPlugin,
-    interaction::InteractionPlugin,
-    ui::UiPlugin,
-    cache::CachePlugin,
-    audio::AudioPlugin,
-};
+use bubble_collapse::plugins::embedding::BubbleCollapsePlugins;
+use bubble_collapse::cli::{LaunchOptions, USAGE};
+use bubble_collapse::resources::benchmark::{Benchmark, BENCHMARK_SEED, DEFAULT_BENCHMARK_TICKS};
+use bubble_collapse::resources::run::RunSeed;
 use winit::window::Icon;
 
 fn main() {
-    App::new()
-        .insert_resource(ClearColor(Color::srgb(0.3, 0.5, 0.8)))
-        .add_plugins(DefaultPlugins.set(AssetPlugin {
-            meta_check: bevy::asset::AssetMetaCheck::Never,
-            ..default()
-        }).set(WindowPlugin {
-            primary_window: Some(Window {
-                title: "Bubble Collapse".to_string(),
+    let options = match LaunchOptions::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("{}\n\n{}", error, USAGE);
+            std::process::exit(2);
+        }
+    };
+    if options.help {
+        println!("{}", USAGE);
+        return;
+    }
+
+    let mut app = App::new();
+    if options.runs_headless() {
+        app
+            .add_plugins(DefaultPlugins.set(AssetPlugin {
+                meta_check: bevy::asset::AssetMetaCheck::Never,
                 ..default()
-            }),
+            }).set(RenderPlugin {
+                render_creation: RenderCreation::Automatic(WgpuSettings {
+                    backends: None,
+                    ..default()
+                }),
+                ..default()
+            }).set(WindowPlugin {
+                primary_window: None,
+                exit_condition: ExitCondition::DontExit,
+                close_when_requested: false,
+            }).disable::<WinitPlugin>())
+            // without winit nothing else keeps the app updating
+            .add_plugins(ScheduleRunnerPlugin::run_loop(Duration::ZERO));
+    } else {
+        let mut window = Window {
+            title: "Bubble Collapse".to_string(),
+            fit_canvas_to_parent: true,
             ..default()
-        }))
-        .add_systems(Startup, set_window_icon)
-        .add_plugins(EguiPlugin)
-        .add_plugins(EntropyPlugin::<WyRand>::default())
+        };
+        if let Some(resolution) = options.resolution {
+            window.resolution = WindowResolution::new(resolution.x, resolution.y);
+        }
+        if options.fullscreen {
+            window.mode = WindowMode::BorderlessFullscreen(MonitorSelection::Current);
+        }
+        app
+            .add_plugins(DefaultPlugins.set(AssetPlugin {
+                meta_check: bevy::asset::AssetMetaCheck::Never,
+                ..default()
+            }).set(WindowPlugin {
+                primary_window: Some(window),
+                ..default()
+            }))
+            .add_systems(Startup, set_window_icon);
+    }
+    // benchmarks quit once they've reported when started from the command line
+    if options.benchmark {
+        app
+            .insert_resource(Benchmark::new(options.ticks.unwrap_or(DEFAULT_BENCHMARK_TICKS), true))
+            .insert_resource(RunSeed { next: Some(BENCHMARK_SEED), ..default() });
+    }
+    app
+        .insert_resource(options)
         .add_plugins(FramepacePlugin)
-        .add_plugins(CameraPlugin)
-        .add_plugins(UiPlugin)
-        .add_plugins(CachePlugin)
-        .add_plugins(GameStatesPlugin)
-        .add_plugins(StatsPlugin)
-        .add_plugins(BubblesPlugin)
-        .add_plugins(PhysicsPlugin)
-        .add_plugins(InteractionPlugin)
-        .add_plugins(AudioPlugin)
+        .add_plugins(BubbleCollapsePlugins::default())
         .run();
 }
 
@@ -0,0 +1,110 @@
+use bevy::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+
+// Small key-value store for settings and progress. Natively every key is a ron
+// file in `saves/`; in the browser it goes into local storage instead.
+
+#[cfg(not(target_arch = "wasm32"))]
+const SAVE_DIR: &str = "saves";
+
+pub fn save<T: Serialize>(key: &str, value: &T) {
+    let data = match ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default()) {
+        Ok(data) => data,
+        Err(error) => {
+            warn!("Failed to serialize {}: {}", key, error);
+            return;
+        }
+    };
+    if let Err(error) = write(key, &data) {
+        warn!("Failed to save {}: {}", key, error);
+    }
+}
+
+pub fn load<T: DeserializeOwned>(key: &str) -> Option<T> {
+    let data = read(key)?;
+    match ron::from_str(&data) {
+        Ok(value) => Some(value),
+        Err(error) => {
+            warn!("Failed to parse saved {}: {}", key, error);
+            None
+        }
+    }
+}
+
+pub fn exists(key: &str) -> bool {
+    read(key).is_some()
+}
+
+pub fn remove(key: &str) {
+    if let Err(error) = delete(key) {
+        warn!("Failed to remove {}: {}", key, error);
+    }
+}
+
+// Natively, files that aren't saves (like the perf CSV) get written next to them
+#[cfg(not(target_arch = "wasm32"))]
+pub fn create_file(name: &str) -> Result<std::fs::File, String> {
+    std::fs::create_dir_all(SAVE_DIR).map_err(|error| error.to_string())?;
+    std::fs::File::create(std::path::Path::new(SAVE_DIR).join(name)).map_err(|error| error.to_string())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn path_for(key: &str) -> std::path::PathBuf {
+    std::path::Path::new(SAVE_DIR).join(format!("{}.ron", key))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write(key: &str, data: &str) -> Result<(), String> {
+    let path = path_for(key);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|error| error.to_string())?;
+    }
+    std::fs::write(path, data).map_err(|error| error.to_string())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read(key: &str) -> Option<String> {
+    std::fs::read_to_string(path_for(key)).ok()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn delete(key: &str) -> Result<(), String> {
+    let path = path_for(key);
+    if !path.exists() {
+        return Ok(());
+    }
+    std::fs::remove_file(path).map_err(|error| error.to_string())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn storage() -> Result<web_sys::Storage, String> {
+    web_sys::window()
+        .ok_or("no window")?
+        .local_storage()
+        .map_err(|_| "local storage unavailable")?
+        .ok_or_else(|| "local storage unavailable".to_string())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn storage_key(key: &str) -> String {
+    format!("bubble_collapse.{}", key)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn write(key: &str, data: &str) -> Result<(), String> {
+    storage()?
+        .set_item(&storage_key(key), data)
+        .map_err(|_| "write failed".to_string())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn read(key: &str) -> Option<String> {
+    storage().ok()?.get_item(&storage_key(key)).ok().flatten()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn delete(key: &str) -> Result<(), String> {
+    storage()?
+        .remove_item(&storage_key(key))
+        .map_err(|_| "remove failed".to_string())
+}
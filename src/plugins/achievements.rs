@@ -0,0 +1,39 @@
+use bevy::prelude::*;
+
+use crate::game_states::{GameState, PausedState};
+use crate::resources::achievements::*;
+use crate::systems::achievements::*;
+use crate::systems::run::run_untainted;
+use crate::systems::save::autosaving;
+use crate::systems::stats::{finish_run_stats, track_run_stats};
+
+pub struct AchievementsPlugin;
+
+impl Plugin for AchievementsPlugin {
+
+    fn build(&self, app: &mut App) {
+        app
+            .add_event::<AchievementUnlockedEvent>()
+            .init_resource::<UnlockedAchievements>()
+            .init_resource::<AchievementToasts>()
+            .add_systems(Startup, load_achievements)
+            // replays, benchmarks, headless runs and runs changed from the
+            // debug window can't unlock anything
+            .add_systems(Update, check_achievements
+                .after(track_run_stats)
+                .run_if(in_state(GameState::InGame))
+                .run_if(in_state(PausedState::Unpaused))
+                .run_if(autosaving)
+                .run_if(run_untainted))
+            .add_systems(OnEnter(GameState::Summary), check_run_over_achievements
+                .after(finish_run_stats)
+                .run_if(autosaving)
+                .run_if(run_untainted))
+            .add_systems(Update, (
+                queue_achievement_toasts,
+                advance_achievement_toasts,
+                save_achievements,
+            ).chain());
+    }
+
+}
@@ -1,6 +1,7 @@
 use bevy::prelude::*;
+use bevy::transform::TransformSystem;
 
-use crate::resources::audio::AudioLimiter;
+use crate::resources::audio::{AudioLimiter, AudioVolumes, ComboPitch, PitchSettings, StereoSettings};
 use crate::systems::audio::*;
 
 pub struct AudioPlugin;
@@ -10,7 +11,32 @@ impl Plugin for AudioPlugin {
     fn build(&self, app: &mut App) {
         app
             .init_resource::<AudioLimiter>()
-            .add_observer(decrease_limiter_on_removal);
+            .init_resource::<AudioVolumes>()
+            .init_resource::<StereoSettings>()
+            .init_resource::<PitchSettings>()
+            .init_resource::<ComboPitch>()
+            .add_observer(decrease_limiter_on_removal)
+            .add_systems(First, reset_audio_spawn_budget)
+            .add_systems(Startup, (
+                load_audio_volumes,
+                load_stereo_settings,
+                load_pitch_settings,
+            ))
+            .add_systems(Update, (
+                save_audio_volumes,
+                save_stereo_settings,
+                save_pitch_settings,
+                reset_combo_pitch,
+                attach_spatial_listener,
+            ))
+            // bevy starts playing queued sounds after transform propagation
+            .add_systems(PostUpdate, (
+                (
+                    apply_panning_to_new_sounds,
+                    apply_volume_to_new_sounds,
+                ).chain(),
+                apply_volume_to_playing_sounds,
+            ).before(TransformSystem::TransformPropagate));
     }
 
 }
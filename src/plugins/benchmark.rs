@@ -0,0 +1,29 @@
+use bevy::prelude::*;
+
+use crate::game_states::GameState;
+use crate::systems::benchmark::*;
+use crate::systems::bubbles::{spawning::init_bubble_spawner, BubbleSystemSet};
+
+pub struct BenchmarkPlugin;
+
+impl Plugin for BenchmarkPlugin {
+
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnEnter(GameState::InGame), setup_benchmark_scenario
+                .after(init_bubble_spawner)
+                .run_if(benchmark_running))
+            .add_systems(First, begin_benchmark_tick
+                .run_if(benchmark_running)
+                .run_if(in_state(GameState::InGame)))
+            .add_systems(Update, drive_benchmark_scenario
+                .after(BubbleSystemSet::Spawning)
+                .before(BubbleSystemSet::Combat)
+                .run_if(benchmark_running)
+                .run_if(in_state(GameState::InGame)))
+            .add_systems(Last, end_benchmark_tick
+                .run_if(benchmark_running)
+                .run_if(in_state(GameState::InGame)));
+    }
+
+}
@@ -18,6 +18,14 @@ impl Plugin for BubblesPlugin {
             .init_resource::<BubbleChances>()
             .add_event::<BubbleDestroyedEvent>()
             .add_event::<BubbleCollapsedEvent>()
+            // the sets share the rng, so they go in a fixed order to draw from it
+            // the same way every frame
+            .configure_sets(Update, (
+                BubbleSystemSet::Spawning,
+                BubbleSystemSet::Movement,
+                BubbleSystemSet::Combat,
+                BubbleSystemSet::Shockwave,
+            ).chain())
             .add_systems(OnEnter(GameState::InGame), init_bubble_spawner)
             .add_systems(OnExit(GameState::InGame), cleanup_everything)
             .add_systems(Update, (
@@ -46,10 +54,12 @@ impl Plugin for BubblesPlugin {
                     .run_if(in_state(GameState::InGame))
                     .run_if(in_state(PausedState::Unpaused)),
                 (
-                    spawn_shockwaves,
+                    (
+                        spawn_shockwaves,
+                        spawn_scatter_shot_shockwaves,
+                    ).chain(),
                     expand_shockwaves,
                     wobble_black_holes,
-                    spawn_scatter_shot_shockwaves,
                     expand_beam,
                 ).in_set(BubbleSystemSet::Shockwave)
                     .run_if(in_state(GameState::InGame))
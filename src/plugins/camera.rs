@@ -1,6 +1,9 @@
 use bevy::prelude::*;
+use bevy::ui::UiSystem;
 
+use crate::resources::camera::Playfield;
 use crate::systems::camera::*;
+use crate::systems::embedding::owns_camera;
 
 pub struct CameraPlugin;
 
@@ -8,7 +11,10 @@ impl Plugin for CameraPlugin {
 
     fn build(&self, app: &mut App) {
         app
-            .add_systems(Startup, spawn_camera);
+            .init_resource::<Playfield>()
+            .add_systems(Startup, spawn_camera.run_if(owns_camera))
+            .add_systems(PreUpdate, update_camera_scaling)
+            .add_systems(PostUpdate, target_menus_at_main_camera.before(UiSystem::Prepare));
     }
 
 }
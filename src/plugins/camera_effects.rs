@@ -0,0 +1,28 @@
+use bevy::prelude::*;
+
+use crate::game_states::{GameState, PausedState};
+use crate::resources::camera_effects::*;
+use crate::systems::bubbles::BubbleSystemSet;
+use crate::systems::camera_effects::*;
+
+pub struct CameraEffectsPlugin;
+
+impl Plugin for CameraEffectsPlugin {
+
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<CameraEffectsSettings>()
+            .init_resource::<CameraEffects>()
+            .add_systems(PreUpdate, attach_camera_effects_anchor)
+            .add_systems(Update, (
+                add_trauma_from_explosions,
+                apply_camera_effects,
+            ).chain()
+                .after(BubbleSystemSet::Combat)
+                .run_if(in_state(GameState::InGame))
+                .run_if(in_state(PausedState::Unpaused)))
+            .add_systems(OnEnter(PausedState::Paused), reset_camera_effects)
+            .add_systems(OnExit(GameState::InGame), reset_camera_effects);
+    }
+
+}
@@ -0,0 +1,53 @@
+use bevy::prelude::*;
+
+use crate::game_states::{DebugState, GameState};
+use crate::resources::debug::PerfStats;
+use crate::systems::benchmark::benchmark_running;
+use crate::systems::bubbles::BubbleSystemSet;
+use crate::systems::debug::*;
+use crate::systems::ui::debug::ui_perf_hud;
+
+pub struct DebugPlugin;
+
+impl Plugin for DebugPlugin {
+
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<PerfStats>()
+            .add_systems(First, record_frame_time
+                .run_if(in_state(DebugState::Debug)))
+            .add_systems(Update, ui_perf_hud
+                .run_if(in_state(DebugState::Debug)))
+            .add_systems(Update, (
+                draw_collider_gizmos,
+                draw_swept_collision_gizmos,
+                draw_beam_gizmos,
+                draw_black_hole_gizmos,
+                draw_bounds_gizmos,
+            ).run_if(in_state(DebugState::Debug))
+                .run_if(in_state(GameState::InGame))
+                .run_if(gizmos_enabled));
+
+        #[cfg(not(target_arch = "wasm32"))]
+        app
+            .add_systems(Last, write_perf_csv
+                .run_if(in_state(DebugState::Debug)))
+            .add_systems(OnExit(DebugState::Debug), close_perf_csv);
+
+        // timestamps on either side of each set, which benchmarks report on too.
+        // The sets run one after another, so a set's timer only starts once the
+        // set before it is done
+        let sets = BubbleSystemSet::all();
+        for (index, set) in sets.into_iter().enumerate() {
+            let start_timer = match index.checked_sub(1) {
+                Some(previous) => start_set_timer(set).after(sets[previous]).before(set),
+                None => start_set_timer(set).before(set),
+            };
+            app.add_systems(Update, (
+                start_timer,
+                end_set_timer(set).after(set),
+            ).run_if(in_state(DebugState::Debug).or(benchmark_running)));
+        }
+    }
+
+}
@@ -0,0 +1,88 @@
+use bevy::app::PluginGroupBuilder;
+use bevy::prelude::*;
+use bevy_egui::EguiPlugin;
+use bevy_rand::prelude::{EntropyPlugin, WyRand};
+
+use crate::plugins::{
+    achievements::AchievementsPlugin,
+    audio::AudioPlugin,
+    benchmark::BenchmarkPlugin,
+    bubbles::BubblesPlugin,
+    cache::CachePlugin,
+    camera::CameraPlugin,
+    camera_effects::CameraEffectsPlugin,
+    debug::DebugPlugin,
+    game_states::GameStatesPlugin,
+    input::InputPlugin,
+    interaction::InteractionPlugin,
+    leaderboard::LeaderboardPlugin,
+    music::MusicPlugin,
+    particles::ParticlesPlugin,
+    physics::PhysicsPlugin,
+    pop_tools::PopToolsPlugin,
+    popups::PopupsPlugin,
+    replay::ReplayPlugin,
+    run::RunPlugin,
+    save::SavePlugin,
+    stats::StatsPlugin,
+    ui::UiPlugin,
+};
+use crate::resources::embedding::BubbleCollapseConfig;
+
+// The whole game, for adding to an app that already has `DefaultPlugins`
+#[derive(Default)]
+pub struct BubbleCollapsePlugins {
+    pub config: BubbleCollapseConfig,
+}
+
+impl PluginGroup for BubbleCollapsePlugins {
+    fn build(self) -> PluginGroupBuilder {
+        PluginGroupBuilder::start::<Self>()
+            .add(EmbeddingPlugin { config: self.config })
+            .add(CameraPlugin)
+            .add(InputPlugin)
+            .add(UiPlugin)
+            .add(CachePlugin)
+            .add(GameStatesPlugin)
+            .add(StatsPlugin)
+            .add(BubblesPlugin)
+            .add(PhysicsPlugin)
+            .add(InteractionPlugin)
+            .add(AudioPlugin)
+            .add(PopupsPlugin)
+            .add(ParticlesPlugin)
+            .add(CameraEffectsPlugin)
+            .add(PopToolsPlugin)
+            .add(MusicPlugin)
+            .add(DebugPlugin)
+            .add(BenchmarkPlugin)
+            .add(RunPlugin)
+            .add(ReplayPlugin)
+            .add(SavePlugin)
+            .add(AchievementsPlugin)
+            .add(LeaderboardPlugin)
+    }
+}
+
+// Goes first so the other plugins can look at the config while they build
+pub struct EmbeddingPlugin {
+    pub config: BubbleCollapseConfig,
+}
+
+impl Plugin for EmbeddingPlugin {
+
+    fn build(&self, app: &mut App) {
+        // hosts that use these already will have added them themselves
+        if !app.is_plugin_added::<EguiPlugin>() {
+            app.add_plugins(EguiPlugin);
+        }
+        if !app.is_plugin_added::<EntropyPlugin<WyRand>>() {
+            app.add_plugins(EntropyPlugin::<WyRand>::default());
+        }
+        if let Some(clear_color) = self.config.clear_color {
+            app.insert_resource(ClearColor(clear_color));
+        }
+        app.insert_resource(self.config.clone());
+    }
+
+}
@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 
 use crate::game_states::*;
+use crate::systems::embedding::enter_starting_state;
 
 pub struct GameStatesPlugin;
 
@@ -10,7 +11,8 @@ impl Plugin for GameStatesPlugin {
         app
             .init_state::<GameState>()
             .init_state::<DebugState>()
-            .init_state::<PausedState>();
+            .init_state::<PausedState>()
+            .add_systems(Startup, enter_starting_state);
     }
 
 }
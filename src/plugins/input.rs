@@ -0,0 +1,27 @@
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+
+use crate::resources::input::*;
+use crate::systems::embedding::reads_window_input;
+use crate::systems::input::*;
+
+pub struct InputPlugin;
+
+impl Plugin for InputPlugin {
+
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<ActionMap>()
+            .init_resource::<ActionState>()
+            .init_resource::<RebindState>()
+            .add_systems(Startup, load_action_map)
+            .add_systems(PreUpdate, update_action_state
+                .after(InputSystem)
+                .run_if(reads_window_input))
+            .add_systems(Update, (
+                capture_rebind,
+                save_action_map,
+            ).chain());
+    }
+
+}
@@ -1,16 +1,58 @@
 use bevy::prelude::*;
+use bevy::ui::UiSystem;
 
 pub struct InteractionPlugin;
 
+use crate::game_states::{GameState, PausedState};
 use crate::resources::interaction::*;
+use crate::resources::pop_tools::PopTool;
+use crate::systems::bubbles::BubbleSystemSet;
+use crate::systems::embedding::reads_window_input;
+use crate::systems::input::update_action_state;
 use crate::systems::interaction::*;
+use crate::systems::pop_tools::pop_tool_selected;
+use crate::systems::replay::replaying;
+use crate::systems::ui::debug::spawning_bubbles_at_cursor;
 
 impl Plugin for InteractionPlugin {
 
     fn build(&self, app: &mut App) {
         app
             .add_event::<MouseClickEvent>()
-            .add_systems(First, handle_mouse_click);
+            .init_resource::<VirtualCursor>()
+            .init_resource::<MenuFocus>()
+            .init_resource::<TouchSettings>()
+            .init_resource::<PointerCapture>()
+            // after the UI has had its turn, so anything it picked up can be skipped
+            .add_systems(PreUpdate, (
+                update_pointer_capture,
+                (
+                    handle_mouse_click
+                        .run_if(pop_tool_selected(PopTool::Click))
+                        .run_if(not(spawning_bubbles_at_cursor)),
+                    handle_touches,
+                ).run_if(not(replaying))
+                    .run_if(reads_window_input),
+            ).chain()
+                .after(UiSystem::Focus)
+                .after(update_action_state))
+            .add_systems(PreUpdate, (
+                release_virtual_cursor_on_mouse_move.run_if(reads_window_input),
+                navigate_menu_focus
+                    .after(UiSystem::Focus)
+                    .after(update_action_state)
+                    .run_if(in_state(GameState::MainMenu).or(in_state(PausedState::Paused))),
+            ).chain())
+            .add_systems(OnEnter(GameState::InGame), spawn_cursor_reticle)
+            .add_systems(OnExit(GameState::InGame), cleanup_cursor_reticle)
+            .add_systems(Update, (
+                move_virtual_cursor,
+                handle_virtual_cursor_click.run_if(not(replaying)),
+                update_cursor_reticle,
+            ).chain()
+                .before(BubbleSystemSet::Combat)
+                .run_if(in_state(GameState::InGame))
+                .run_if(in_state(PausedState::Unpaused)));
     }
 
 }
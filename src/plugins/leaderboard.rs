@@ -0,0 +1,28 @@
+use bevy::prelude::*;
+
+use crate::game_states::GameState;
+use crate::resources::leaderboard::*;
+use crate::systems::leaderboard::*;
+use crate::systems::run::run_untainted;
+use crate::systems::save::autosaving;
+use crate::systems::stats::finish_run_stats;
+
+pub struct LeaderboardPlugin;
+
+impl Plugin for LeaderboardPlugin {
+
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Leaderboard>()
+            .init_resource::<LeaderboardView>()
+            .add_systems(Startup, load_leaderboard)
+            .add_systems(OnEnter(GameState::Summary), check_leaderboard
+                .after(finish_run_stats)
+                .run_if(autosaving)
+                .run_if(run_untainted))
+            .add_systems(OnExit(GameState::Summary), submit_pending_entry
+                .run_if(resource_exists::<PendingLeaderboardEntry>))
+            .add_systems(Update, save_leaderboard);
+    }
+
+}
@@ -0,0 +1,21 @@
+use bevy::prelude::*;
+
+use crate::resources::music::*;
+use crate::systems::music::*;
+
+pub struct MusicPlugin;
+
+impl Plugin for MusicPlugin {
+
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<MusicSettings>()
+            .init_resource::<MusicMix>()
+            .add_systems(Startup, spawn_music_tracks)
+            .add_systems(Update, (
+                update_music_mix,
+                fade_music_tracks,
+            ).chain());
+    }
+
+}
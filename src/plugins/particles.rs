@@ -0,0 +1,31 @@
+use bevy::prelude::*;
+
+use crate::game_states::{GameState, PausedState};
+use crate::resources::particles::*;
+use crate::systems::bubbles::BubbleSystemSet;
+use crate::systems::particles::*;
+use crate::systems::run::start_run;
+
+pub struct ParticlesPlugin;
+
+impl Plugin for ParticlesPlugin {
+
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<ParticleSettings>()
+            .init_resource::<ParticlePool>()
+            .init_resource::<ParticleRandom>()
+            .add_systems(OnEnter(GameState::InGame), init_particle_pool.after(start_run))
+            .add_systems(OnExit(GameState::InGame), cleanup_particles)
+            .add_systems(Update, (
+                emit_pop_droplets,
+                emit_beam_sparks,
+                emit_black_hole_debris,
+                update_particles,
+            ).chain()
+                .after(BubbleSystemSet::Shockwave)
+                .run_if(in_state(GameState::InGame))
+                .run_if(in_state(PausedState::Unpaused)));
+    }
+
+}
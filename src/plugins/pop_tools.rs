@@ -0,0 +1,48 @@
+use bevy::prelude::*;
+
+use crate::game_states::{GameState, PausedState};
+use crate::resources::pop_tools::*;
+use crate::systems::bubbles::BubbleSystemSet;
+use crate::systems::embedding::reads_window_input;
+use crate::systems::interaction::handle_mouse_click;
+use crate::systems::pop_tools::*;
+use crate::systems::replay::replaying;
+use crate::systems::ui::debug::spawning_bubbles_at_cursor;
+
+pub struct PopToolsPlugin;
+
+impl Plugin for PopToolsPlugin {
+
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<PopTools>()
+            .init_resource::<SweepTool>()
+            .init_resource::<ChargeTool>()
+            .init_resource::<AreaPopTool>()
+            .add_systems(OnEnter(GameState::InGame), (
+                reset_pop_tools,
+                spawn_pop_tool_indicator,
+            ))
+            .add_systems(OnExit(GameState::InGame), cleanup_pop_tool_indicator)
+            // these stand in for `handle_mouse_click` while their tool is selected
+            .add_systems(PreUpdate, (
+                sweep_pop.run_if(pop_tool_selected(PopTool::Sweep)),
+                charge_pop.run_if(pop_tool_selected(PopTool::Charge)),
+                area_pop.run_if(pop_tool_selected(PopTool::Area)),
+            ).after(handle_mouse_click)
+                .run_if(not(spawning_bubbles_at_cursor))
+                .run_if(not(replaying))
+                .run_if(reads_window_input)
+                .run_if(in_state(GameState::InGame))
+                .run_if(in_state(PausedState::Unpaused)))
+            .add_systems(Update, (
+                cycle_pop_tool,
+                tick_pop_tool_cooldowns,
+                update_pop_tool_indicator,
+            ).chain()
+                .before(BubbleSystemSet::Combat)
+                .run_if(in_state(GameState::InGame))
+                .run_if(in_state(PausedState::Unpaused)));
+    }
+
+}
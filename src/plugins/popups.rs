@@ -0,0 +1,26 @@
+use bevy::prelude::*;
+
+use crate::game_states::{GameState, PausedState};
+use crate::resources::popups::ScorePopupPool;
+use crate::systems::bubbles::BubbleSystemSet;
+use crate::systems::popups::*;
+
+pub struct PopupsPlugin;
+
+impl Plugin for PopupsPlugin {
+
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<ScorePopupPool>()
+            .add_systems(OnEnter(GameState::InGame), init_score_popup_pool)
+            .add_systems(OnExit(GameState::InGame), cleanup_score_popups)
+            .add_systems(Update, (
+                spawn_score_popups,
+                animate_score_popups,
+            ).chain()
+                .after(BubbleSystemSet::Combat)
+                .run_if(in_state(GameState::InGame))
+                .run_if(in_state(PausedState::Unpaused)));
+    }
+
+}
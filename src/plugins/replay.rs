@@ -0,0 +1,53 @@
+use bevy::prelude::*;
+use bevy::time::TimeSystem;
+
+use crate::game_states::{GameState, PausedState};
+use crate::resources::replay::ReplayRecorder;
+use crate::systems::benchmark::benchmark_running;
+use crate::systems::bubbles::BubbleSystemSet;
+use crate::systems::interaction::handle_mouse_click;
+use crate::systems::replay::*;
+use crate::systems::run::{count_down_tick_limit, start_run};
+use crate::systems::save::resuming_run;
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnEnter(GameState::InGame), forget_recorded_replay)
+            // every run gets recorded, apart from replays themselves, benchmarks
+            // and resumed runs, which don't start from their seed
+            .add_systems(OnEnter(GameState::InGame), start_recording
+                .after(start_run)
+                .run_if(not(replaying))
+                .run_if(not(benchmark_running))
+                .run_if(not(resuming_run)))
+            .add_systems(Last, record_replay_frame
+                .run_if(resource_exists::<ReplayRecorder>)
+                .run_if(in_state(GameState::InGame))
+                .run_if(in_state(PausedState::Unpaused)))
+            .add_systems(OnExit(GameState::InGame), save_recording)
+            // quitting straight out of a run never leaves `InGame`
+            .add_systems(Last, save_recording
+                .after(record_replay_frame)
+                .after(count_down_tick_limit)
+                .run_if(on_event::<AppExit>))
+            .add_systems(First, set_replay_time
+                .before(TimeSystem)
+                .run_if(replaying))
+            .add_systems(PreUpdate, play_replay_pops
+                .after(handle_mouse_click)
+                .run_if(replaying)
+                .run_if(in_state(GameState::InGame)))
+            .add_systems(Update, play_replay_purchases
+                .before(BubbleSystemSet::Combat)
+                .run_if(replaying)
+                .run_if(in_state(GameState::InGame)))
+            .add_systems(Last, advance_replay
+                .run_if(replaying)
+                .run_if(in_state(GameState::InGame)));
+    }
+
+}
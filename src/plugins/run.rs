@@ -0,0 +1,30 @@
+use bevy::prelude::*;
+
+use crate::game_states::{GameState, PausedState};
+use crate::resources::run::*;
+use crate::systems::bubbles::spawning::init_bubble_spawner;
+use crate::systems::embedding::enter_starting_state;
+use crate::systems::run::*;
+
+pub struct RunPlugin;
+
+impl Plugin for RunPlugin {
+
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<GameMode>()
+            .init_resource::<RunSeed>()
+            .init_resource::<RunTimer>()
+            .init_resource::<RunTainted>()
+            // the command line has the last word on where the game starts
+            .add_systems(Startup, apply_launch_options.after(enter_starting_state))
+            // before anything in the run gets a chance to use the rng
+            .add_systems(OnEnter(GameState::InGame), start_run.before(init_bubble_spawner))
+            .add_systems(Update, tick_run_timer
+                .run_if(in_state(GameState::InGame))
+                .run_if(in_state(PausedState::Unpaused)))
+            .add_systems(Last, count_down_tick_limit
+                .run_if(resource_exists::<TickLimit>));
+    }
+
+}
@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+
+use crate::game_states::{GameState, PausedState};
+use crate::systems::bubbles::spawning::init_bubble_spawner;
+use crate::systems::replay::start_recording;
+use crate::systems::run::start_run;
+use crate::systems::save::*;
+use crate::systems::stats::init_stats;
+use crate::systems::ui::upgrades_menu::reset_upgrades;
+
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+
+    fn build(&self, app: &mut App) {
+        app
+            // after everything that sets up a fresh run, so none of it undoes the restore
+            .add_systems(OnEnter(GameState::InGame), restore_run
+                .after(start_run)
+                .after(start_recording)
+                .after(init_stats)
+                .after(reset_upgrades)
+                .after(init_bubble_spawner)
+                .run_if(resuming_run))
+            .add_systems(OnEnter(PausedState::Paused), save_run
+                .run_if(in_state(GameState::InGame))
+                .run_if(autosaving))
+            .add_systems(Last, save_run
+                .run_if(in_state(GameState::InGame))
+                .run_if(on_event::<AppExit>)
+                .run_if(autosaving))
+            .add_systems(OnEnter(GameState::Summary), discard_saved_run
+                .run_if(autosaving));
+    }
+
+}
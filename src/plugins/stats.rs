@@ -1,7 +1,9 @@
 use bevy::prelude::*;
 
-use crate::game_states::GameState;
+use crate::game_states::{GameState, PausedState};
 use crate::resources::stats::*;
+use crate::systems::run::run_untainted;
+use crate::systems::save::autosaving;
 use crate::systems::stats::*;
 
 pub struct StatsPlugin;
@@ -11,6 +13,8 @@ impl Plugin for StatsPlugin {
     fn build(&self, app: &mut App) {
         app
             .init_resource::<GameStats>()
+            .init_resource::<Chain>()
+            .init_resource::<RunStats>()
             .add_systems(OnEnter(GameState::InGame), (
                 init_stats,
                 draw_score,
@@ -20,7 +24,19 @@ impl Plugin for StatsPlugin {
                 update_score,
                 increment_score_for_destroyed_bubbles,
                 unlock_upgrades,
-            ).run_if(in_state(GameState::InGame)));
+            ).run_if(in_state(GameState::InGame)))
+            .add_systems(Update, (
+                track_chain,
+                track_run_stats,
+            ).chain()
+                .run_if(in_state(GameState::InGame))
+                .run_if(in_state(PausedState::Unpaused)))
+            .add_systems(OnEnter(GameState::Summary), (
+                finish_run_stats,
+                record_run_history
+                    .run_if(autosaving)
+                    .run_if(run_untainted),
+            ).chain());
     }
 
 }
@@ -1,6 +1,9 @@
 use bevy::prelude::*;
 
+use crate::resources::debug::DebugTools;
 use crate::resources::ui::{UpgradeChangedEvent, UpgradesMenuInfo};
+use crate::systems::bubbles::BubbleSystemSet;
+use crate::systems::replay::replaying;
 use crate::systems::ui::*;
 use crate::game_states::{DebugState, GameState, PausedState};
 
@@ -12,29 +15,51 @@ impl Plugin for UiPlugin {
         app
             .add_event::<UpgradeChangedEvent>()
             .init_resource::<UpgradesMenuInfo>()
+            .init_resource::<DebugTools>()
             .add_systems(Update, (
                 (
-                    debug::ui_debug,
-                    debug::toggle_debug_off_on_backslash,
+                    (
+                        debug::ui_debug,
+                        debug::apply_debug_time,
+                    ).chain(),
+                    debug::toggle_debug_off_on_input,
                 )
                     .run_if(in_state(DebugState::Debug)),
-                debug::toggle_debug_on_on_backslash
+                debug::toggle_debug_on_on_input
                     .run_if(in_state(DebugState::NoDebug)),
             ))
+            .add_systems(OnExit(DebugState::Debug), debug::reset_debug_tools)
+            .add_systems(Update, debug::spawn_bubble_at_cursor
+                .before(BubbleSystemSet::Combat)
+                .run_if(in_state(DebugState::Debug))
+                .run_if(in_state(GameState::InGame))
+                .run_if(in_state(PausedState::Unpaused)))
             .add_systems(OnEnter(GameState::MainMenu), main_menu::draw_main_menu)
             .add_systems(OnExit(GameState::MainMenu), main_menu::cleanup_main_menu)
             .add_systems(Update, (
                 main_menu::button_system,
-            ).run_if(in_state(GameState::MainMenu)))
+                main_menu::update_mode_button,
+            ).chain().run_if(in_state(GameState::MainMenu)))
+            .add_systems(Update, (
+                options_menu::ui_options_menu,
+            ).run_if(in_state(GameState::Options)))
+            .add_systems(Update, achievements::ui_achievements
+                .run_if(in_state(GameState::Achievements)))
+            .add_systems(Update, achievements::ui_achievement_toasts)
+            .add_systems(Update, leaderboard::ui_leaderboard
+                .run_if(in_state(GameState::Leaderboard)))
+            .add_systems(Update, summary::ui_summary
+                .run_if(in_state(GameState::Summary)))
             .add_systems(OnEnter(PausedState::Paused), pause_menu::draw_pause_menu)
             .add_systems(OnExit(PausedState::Paused), pause_menu::cleanup_pause_menu)
             .add_systems(Update, (
                 pause_menu::button_system,
             ).run_if(in_state(PausedState::Paused)))
             .add_systems(Update, (
-                pause_menu::pause_game_on_esc.run_if(in_state(PausedState::Unpaused)),
-                pause_menu::unpause_game_on_esc.run_if(in_state(PausedState::Paused)),
-            ).run_if(in_state(GameState::InGame)))
+                pause_menu::pause_game_on_input.run_if(in_state(PausedState::Unpaused)),
+                pause_menu::unpause_game_on_input.run_if(in_state(PausedState::Paused)),
+            ).run_if(in_state(GameState::InGame))
+                .run_if(not(replaying)))
             .add_systems(OnExit(GameState::InGame), pause_menu::unpause_game)
             .add_systems(OnEnter(GameState::InGame), (
                 upgrades_menu::draw_upgrades_menu,
@@ -42,9 +67,17 @@ impl Plugin for UiPlugin {
             ))
             .add_systems(OnExit(GameState::InGame), upgrades_menu::cleanup_upgrades_menu)
             .add_systems(Update, (
-                upgrades_menu::button_interactions,
+                upgrades_menu::button_interactions.run_if(not(replaying)),
+                upgrades_menu::auto_buy_button_interactions,
                 upgrades_menu::update_upgrades_menu,
-            ).run_if(in_state(GameState::InGame)));
+                upgrades_menu::adapt_upgrades_menu_layout,
+            ).run_if(in_state(GameState::InGame)))
+            .add_systems(Update, (
+                upgrades_menu::buy_upgrades_on_input,
+                upgrades_menu::auto_buy_upgrades,
+            ).run_if(in_state(GameState::InGame))
+                .run_if(in_state(PausedState::Unpaused))
+                .run_if(not(replaying)));
     }
 
 }
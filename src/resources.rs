@@ -4,3 +4,18 @@ pub mod interaction;
 pub mod ui;
 pub mod cache;
 pub mod audio;
+pub mod popups;
+pub mod particles;
+pub mod camera_effects;
+pub mod camera;
+pub mod input;
+pub mod pop_tools;
+pub mod music;
+pub mod debug;
+pub mod benchmark;
+pub mod run;
+pub mod replay;
+pub mod embedding;
+pub mod save;
+pub mod achievements;
+pub mod leaderboard;
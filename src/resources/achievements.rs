@@ -0,0 +1,146 @@
+use std::collections::VecDeque;
+
+use bevy::{prelude::*, utils::HashMap};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    components::bubbles::BubbleType,
+    resources::{run::GameMode, stats::RunStats, ui::UpgradesMenuInfo},
+    systems::ui::upgrades_menu::UpgradesMenuAction,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Achievement {
+    FirstPop,
+    ChainReaction,
+    Cascade,
+    FiveFigures,
+    HandsOffTheTap,
+    EventHorizon,
+    Shopaholic,
+    AgainstTheClock,
+}
+
+impl Achievement {
+    pub fn all() -> [Achievement; 8] {
+        [
+            Achievement::FirstPop,
+            Achievement::ChainReaction,
+            Achievement::Cascade,
+            Achievement::FiveFigures,
+            Achievement::HandsOffTheTap,
+            Achievement::EventHorizon,
+            Achievement::Shopaholic,
+            Achievement::AgainstTheClock,
+        ]
+    }
+
+    // Stable across renames, so it's what gets saved
+    pub fn id(&self) -> &'static str {
+        match self {
+            Achievement::FirstPop => "first_pop",
+            Achievement::ChainReaction => "chain_reaction",
+            Achievement::Cascade => "cascade",
+            Achievement::FiveFigures => "five_figures",
+            Achievement::HandsOffTheTap => "hands_off_the_tap",
+            Achievement::EventHorizon => "event_horizon",
+            Achievement::Shopaholic => "shopaholic",
+            Achievement::AgainstTheClock => "against_the_clock",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Achievement::FirstPop => "First Pop",
+            Achievement::ChainReaction => "Chain Reaction",
+            Achievement::Cascade => "Cascade",
+            Achievement::FiveFigures => "Five Figures",
+            Achievement::HandsOffTheTap => "Hands Off the Tap",
+            Achievement::EventHorizon => "Event Horizon",
+            Achievement::Shopaholic => "Shopaholic",
+            Achievement::AgainstTheClock => "Against the Clock",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Achievement::FirstPop => "Pop a bubble",
+            Achievement::ChainReaction => "Pop 100 bubbles in one chain",
+            Achievement::Cascade => "Pop 1000 bubbles in one chain",
+            Achievement::FiveFigures => "Reach 10000 score",
+            Achievement::HandsOffTheTap => "Reach 10000 score without buying Increase Rate",
+            Achievement::EventHorizon => "Score 250 from black holes in one run",
+            Achievement::Shopaholic => "Buy 25 upgrades in one run",
+            Achievement::AgainstTheClock => "Finish a Time Attack run with at least 1000 score",
+        }
+    }
+
+    pub fn is_met(&self, context: &AchievementContext) -> bool {
+        let stats = context.stats;
+        match self {
+            Achievement::FirstPop => stats.user_pops + stats.effect_pops > 0,
+            Achievement::ChainReaction => stats.longest_chain >= 100,
+            Achievement::Cascade => stats.longest_chain >= 1000,
+            Achievement::FiveFigures => context.score >= 10_000,
+            Achievement::HandsOffTheTap => context.score >= 10_000
+                && context.upgrades.purchase_count(UpgradesMenuAction::IncreaseRate) == 0,
+            Achievement::EventHorizon => stats.score_by_source.get(&BubbleType::BlackHole).is_some_and(|score| *score >= 250),
+            Achievement::Shopaholic => context.upgrades.purchases.values().sum::<u32>() >= 25,
+            Achievement::AgainstTheClock => context.run_over
+                && context.mode == GameMode::TimeAttack
+                && context.score >= 1000,
+        }
+    }
+}
+
+// What the conditions get to look at
+pub struct AchievementContext<'a> {
+    pub mode: GameMode,
+    pub score: i32,
+    pub stats: &'a RunStats,
+    pub upgrades: &'a UpgradesMenuInfo,
+    // only set once the run has ended
+    pub run_over: bool,
+}
+
+// Unlocked achievements by id, with the unix time they were unlocked
+#[derive(Resource, Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UnlockedAchievements {
+    pub unlocked: HashMap<String, u64>,
+}
+
+impl UnlockedAchievements {
+    pub const SAVE_KEY: &'static str = "achievements";
+
+    pub fn unlocked_at(&self, achievement: Achievement) -> Option<u64> {
+        self.unlocked.get(achievement.id()).copied()
+    }
+
+    pub fn is_unlocked(&self, achievement: Achievement) -> bool {
+        self.unlocked.contains_key(achievement.id())
+    }
+
+    pub fn unlock(&mut self, achievement: Achievement, date: u64) {
+        self.unlocked.insert(achievement.id().to_string(), date);
+    }
+
+    pub fn count(&self) -> usize {
+        Achievement::all().iter().filter(|achievement| self.is_unlocked(**achievement)).count()
+    }
+}
+
+#[derive(Event, Debug)]
+pub struct AchievementUnlockedEvent {
+    pub achievement: Achievement,
+}
+
+// Unlock notifications waiting to be shown, the front one is on screen
+#[derive(Resource, Debug, Default)]
+pub struct AchievementToasts {
+    pub queue: VecDeque<Achievement>,
+    pub timer: Timer,
+}
+
+impl AchievementToasts {
+    pub const DURATION: f32 = 4.0;
+}
@@ -1,11 +1,198 @@
 use bevy::{prelude::*, utils::HashMap};
+use serde::{Deserialize, Serialize};
 
-use crate::components::audio::AudioLimitRemovalFlag;
+use crate::components::audio::{AudioBus, AudioLimitRemovalFlag};
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AudioCategory {
+    Sfx,
+    Music,
+    Ui,
+}
+
+impl AudioCategory {
+    pub fn all() -> [AudioCategory; 3] {
+        [AudioCategory::Sfx, AudioCategory::Music, AudioCategory::Ui]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            AudioCategory::Sfx => "Effects",
+            AudioCategory::Music => "Music",
+            AudioCategory::Ui => "Interface",
+        }
+    }
+}
+
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AudioVolumes {
+    pub master: f32,
+    pub sfx: f32,
+    pub music: f32,
+    pub ui: f32,
+}
+
+impl Default for AudioVolumes {
+    fn default() -> Self {
+        Self {
+            master: 1.0,
+            sfx: 1.0,
+            music: 1.0,
+            ui: 1.0,
+        }
+    }
+}
+
+impl AudioVolumes {
+    pub const SAVE_KEY: &'static str = "audio";
+
+    pub fn category_mut(&mut self, category: AudioCategory) -> &mut f32 {
+        match category {
+            AudioCategory::Sfx => &mut self.sfx,
+            AudioCategory::Music => &mut self.music,
+            AudioCategory::Ui => &mut self.ui,
+        }
+    }
+
+    // The multiplier for everything in a category, master volume included
+    pub fn volume_for(&self, category: AudioCategory) -> f32 {
+        let volume = match category {
+            AudioCategory::Sfx => self.sfx,
+            AudioCategory::Music => self.music,
+            AudioCategory::Ui => self.ui,
+        };
+        self.master * volume
+    }
+}
+
+#[derive(Resource, Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StereoSettings {
+    pub mono: bool,
+}
+
+impl StereoSettings {
+    pub const SAVE_KEY: &'static str = "stereo";
+}
+
+const KEY_NAMES: [&str; 12] = ["C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B"];
+// How far above the key's root a chain can climb before it stays on the top note
+const PITCH_RANGE: u8 = 18;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MusicalScale {
+    #[default]
+    MajorPentatonic,
+    MinorPentatonic,
+    Major,
+    Minor,
+}
+
+impl MusicalScale {
+    pub fn all() -> [MusicalScale; 4] {
+        [MusicalScale::MajorPentatonic, MusicalScale::MinorPentatonic, MusicalScale::Major, MusicalScale::Minor]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MusicalScale::MajorPentatonic => "Major Pentatonic",
+            MusicalScale::MinorPentatonic => "Minor Pentatonic",
+            MusicalScale::Major => "Major",
+            MusicalScale::Minor => "Minor",
+        }
+    }
+
+    // Semitones above the root within one octave
+    pub fn intervals(&self) -> &'static [u8] {
+        match self {
+            MusicalScale::MajorPentatonic => &[0, 2, 4, 7, 9],
+            MusicalScale::MinorPentatonic => &[0, 3, 5, 7, 10],
+            MusicalScale::Major => &[0, 2, 4, 5, 7, 9, 11],
+            MusicalScale::Minor => &[0, 2, 3, 5, 7, 8, 10],
+        }
+    }
+}
+
+// The pop sample counts as a C, the key shifts everything from there
+#[derive(Resource, Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PitchSettings {
+    // semitones above C
+    pub key: u8,
+    pub scale: MusicalScale,
+}
+
+impl PitchSettings {
+    pub const SAVE_KEY: &'static str = "pitch";
+
+    pub fn key_name(key: u8) -> &'static str {
+        KEY_NAMES[key as usize % 12]
+    }
+
+    // Playback speed for the `step`th note of a chain. Roots above F# are taken
+    // from the octave below, so the first note stays close to the sample's pitch
+    pub fn speed_for_step(&self, step: u32) -> f32 {
+        let intervals = self.scale.intervals();
+        let notes = (0..)
+            .map(|note| intervals[note % intervals.len()] + 12 * (note / intervals.len()) as u8)
+            .take_while(|semitones| *semitones <= PITCH_RANGE)
+            .collect::<Vec<u8>>();
+        let semitones = notes[(step as usize).min(notes.len() - 1)] as i32;
+        let root = if self.key > 6 { self.key as i32 - 12 } else { self.key as i32 };
+        2.0_f32.powf((root + semitones) as f32 / 12.0)
+    }
+}
+
+// Counts the pops of the current chain, so each one plays the next note up
 #[derive(Resource, Debug, Default, Clone)]
+pub struct ComboPitch {
+    pub step: u32,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AudioPriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+    Critical,
+}
+
+// What to cut off when a new sound doesn't fit. Only voices of the same or
+// lower priority are ever stolen
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum StealPolicy {
+    None,
+    #[default]
+    Oldest,
+    Quietest,
+}
+
+#[derive(Debug, Clone)]
+struct Voice {
+    entity: Entity,
+    audio: Handle<AudioSource>,
+    priority: AudioPriority,
+    volume: f32,
+    started: u64,
+}
+
+#[derive(Resource, Debug, Clone)]
 pub struct AudioLimiter {
     max_counts: HashMap<Handle<AudioSource>, u32>,
     current_counts: HashMap<Handle<AudioSource>, u32>,
+    priorities: HashMap<Handle<AudioSource>, AudioPriority>,
+    voices: Vec<Voice>,
+    next_voice: u64,
+    pub steal_policy: StealPolicy,
+    // across every sound, no matter the handle
+    pub max_voices: usize,
+    pub max_spawns_per_frame: u32,
+    spawned_this_frame: u32,
+}
+
+impl Default for AudioLimiter {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl AudioLimiter {
@@ -13,6 +200,13 @@ impl AudioLimiter {
         Self {
             max_counts: HashMap::new(),
             current_counts: HashMap::new(),
+            priorities: HashMap::new(),
+            voices: Vec::new(),
+            next_voice: 0,
+            steal_policy: StealPolicy::default(),
+            max_voices: 32,
+            max_spawns_per_frame: 8,
+            spawned_this_frame: 0,
         }
     }
 
@@ -24,6 +218,22 @@ impl AudioLimiter {
         self.max_counts.get(&audio).cloned()
     }
 
+    pub fn set_priority(&mut self, audio: Handle<AudioSource>, priority: AudioPriority) {
+        self.priorities.insert(audio, priority);
+    }
+
+    pub fn get_priority(&self, audio: Handle<AudioSource>) -> AudioPriority {
+        self.priorities.get(&audio).cloned().unwrap_or_default()
+    }
+
+    pub fn voice_count(&self) -> usize {
+        self.voices.len()
+    }
+
+    pub fn new_frame(&mut self) {
+        self.spawned_this_frame = 0;
+    }
+
     pub fn decrease_count(&mut self, audio: Handle<AudioSource>) {
         if let Some(count) = self.current_counts.get_mut(&audio) {
             if *count > 0 {
@@ -32,26 +242,92 @@ impl AudioLimiter {
         }
     }
 
+    // Forgets a voice once its entity goes away. Stolen voices are released
+    // straight away, so releasing them again when they despawn does nothing
+    pub fn release(&mut self, entity: Entity) {
+        let Some(index) = self.voices.iter().position(|voice| voice.entity == entity) else {
+            return;
+        };
+        let voice = self.voices.swap_remove(index);
+        self.decrease_count(voice.audio);
+    }
+
     pub fn play_if_allowed<'a>(
         &mut self,
         commands: &'a mut Commands,
         audio: Handle<AudioSource>,
+        category: AudioCategory,
+        position: Option<Vec2>,
         playback_settings: PlaybackSettings,
     ) -> Option<EntityCommands<'a>> {
+        let priority = self.get_priority(audio.clone());
+        // high priority sounds always get through, and don't use up the budget
+        let budgeted = priority < AudioPriority::High;
+        if budgeted && self.spawned_this_frame >= self.max_spawns_per_frame {
+            return None;
+        }
+
         let limit = self.get_limit(audio.clone()).unwrap_or(u32::MAX);
+        let count = *self.current_counts.get(&audio).unwrap_or(&0);
+        if count >= limit && !self.steal(commands, priority, Some(&audio)) {
+            return None;
+        }
+        if self.voices.len() >= self.max_voices && !self.steal(commands, priority, None) {
+            return None;
+        }
 
-        {
-            let count = self.current_counts.entry(audio.clone()).or_insert(0);
-            if *count >= limit {
-                return None;
-            }
-            *count += 1;
+        *self.current_counts.entry(audio.clone()).or_insert(0) += 1;
+        if budgeted {
+            self.spawned_this_frame += 1;
         }
 
-        Some(commands.spawn((
-            AudioPlayer::new(audio),
+        let mut sound = commands.spawn((
+            AudioPlayer::new(audio.clone()),
             playback_settings,
+            AudioBus::new(category, playback_settings.volume),
             AudioLimitRemovalFlag,
-        )))
+        ));
+        // positioned sounds get panned, see `apply_panning_to_new_sounds`
+        if let Some(position) = position {
+            sound.insert(Transform::from_translation(position.extend(0.0)));
+        }
+
+        self.voices.push(Voice {
+            entity: sound.id(),
+            audio,
+            priority,
+            volume: playback_settings.volume.get(),
+            started: self.next_voice,
+        });
+        self.next_voice += 1;
+        Some(sound)
+    }
+
+    // Frees up a voice for a sound of `priority`, only looking at voices of
+    // `audio` if it's given. Returns false if nothing could be stolen
+    fn steal(
+        &mut self,
+        commands: &mut Commands,
+        priority: AudioPriority,
+        audio: Option<&Handle<AudioSource>>,
+    ) -> bool {
+        let candidates = self.voices.iter()
+            .filter(|voice| voice.priority <= priority)
+            .filter(|voice| audio.is_none_or(|audio| voice.audio == *audio));
+        // lower priorities always go first, the policy only breaks ties
+        let victim = match self.steal_policy {
+            StealPolicy::None => None,
+            StealPolicy::Oldest => candidates
+                .min_by_key(|voice| (voice.priority, voice.started)),
+            StealPolicy::Quietest => candidates
+                .min_by(|a, b| a.priority.cmp(&b.priority).then(a.volume.total_cmp(&b.volume))),
+        };
+        let Some(entity) = victim.map(|voice| voice.entity) else {
+            return false;
+        };
+
+        self.release(entity);
+        commands.entity(entity).despawn_recursive();
+        true
     }
 }
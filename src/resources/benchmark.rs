@@ -0,0 +1,98 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, utils::{HashMap, Instant}};
+
+use crate::{resources::debug::PerfStats, systems::bubbles::BubbleSystemSet};
+
+pub const DEFAULT_BENCHMARK_TICKS: u32 = 1800;
+// every benchmark plays the same layout, unless `--seed` picks another
+pub const BENCHMARK_SEED: u64 = 2025;
+
+// A scripted stress run: the playfield gets flooded with bubbles and special
+// bubbles keep getting set off, while every tick is timed. Time advances by
+// exactly one tick per frame, so runs are comparable however slow they are
+#[derive(Resource, Debug)]
+pub struct Benchmark {
+    pub ticks: u32,
+    pub tick_rate: f64,
+    pub initial_bubbles: u32,
+    pub spawn_rate: u64,
+    // how often a batch of special bubbles gets popped
+    pub trigger_interval: u32,
+    pub exit_when_done: bool,
+    pub elapsed_ticks: u32,
+    tick_start: Option<Instant>,
+    pub tick_times: Vec<Duration>,
+    pub set_times: HashMap<BubbleSystemSet, Vec<Duration>>,
+}
+
+impl Benchmark {
+    pub fn new(ticks: u32, exit_when_done: bool) -> Self {
+        Self {
+            ticks,
+            tick_rate: 60.0,
+            initial_bubbles: 3000,
+            spawn_rate: 400,
+            trigger_interval: 30,
+            exit_when_done,
+            elapsed_ticks: 0,
+            tick_start: None,
+            tick_times: Vec::new(),
+            set_times: HashMap::new(),
+        }
+    }
+
+    pub fn tick_duration(&self) -> Duration {
+        Duration::from_secs_f64(1.0 / self.tick_rate)
+    }
+
+    pub fn start_tick(&mut self) {
+        self.tick_start = Some(Instant::now());
+    }
+
+    pub fn end_tick(&mut self, perf_stats: &PerfStats) {
+        let Some(start) = self.tick_start.take() else {
+            return;
+        };
+        self.tick_times.push(start.elapsed());
+        for set in BubbleSystemSet::all() {
+            self.set_times.entry(set).or_default().push(perf_stats.set_time(set));
+        }
+        self.elapsed_ticks += 1;
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.elapsed_ticks >= self.ticks
+    }
+
+    pub fn report(&self) -> String {
+        let mut lines = vec![
+            format!("Benchmark: {} ticks at {} Hz", self.elapsed_ticks, self.tick_rate),
+            format!("  {:<10} {}", "Tick", summarize(&self.tick_times)),
+        ];
+        for set in BubbleSystemSet::all() {
+            let times = self.set_times.get(&set).map(Vec::as_slice).unwrap_or_default();
+            lines.push(format!("  {:<10} {}", set.name(), summarize(times)));
+        }
+        lines.join("\n")
+    }
+}
+
+fn summarize(times: &[Duration]) -> String {
+    if times.is_empty() {
+        return "no samples".to_string();
+    }
+    let mut sorted = times.to_vec();
+    sorted.sort();
+    let percentile = |p: f64| sorted[((sorted.len() - 1) as f64 * p).round() as usize];
+    let mean = sorted.iter().sum::<Duration>() / sorted.len() as u32;
+    let ms = |duration: Duration| duration.as_secs_f64() * 1000.0;
+    format!(
+        "mean {:.3} ms, p50 {:.3} ms, p95 {:.3} ms, p99 {:.3} ms, max {:.3} ms",
+        ms(mean),
+        ms(percentile(0.5)),
+        ms(percentile(0.95)),
+        ms(percentile(0.99)),
+        ms(sorted[sorted.len() - 1]),
+    )
+}
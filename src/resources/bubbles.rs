@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 use bevy::prelude::*;
 
@@ -9,15 +9,16 @@ pub struct BubbleSpawnTimer {
     pub action_timer: ActionTimer,
 }
 
+// Ordered, so the same random number picks the same type in every run
 #[derive(Resource, Debug, Default)]
 pub struct BubbleChances {
-    pub chances: HashMap<BubbleType, f32>,
+    pub chances: BTreeMap<BubbleType, f32>,
 }
 
 impl BubbleChances {
     pub fn new() -> Self {
         Self {
-            chances: HashMap::new(),
+            chances: BTreeMap::new(),
         }
     }
 
@@ -53,10 +54,13 @@ pub struct BubbleDestroyedEvent {
     pub radius: f32,
     pub color: Color,
     pub bubble_type: BubbleType,
+    pub triggered_by_user: bool,
 }
 
 #[derive(Event, Debug, Default)]
 pub struct BubbleCollapsedEvent {
     pub triggered_by_user: bool,
+    // the kind of bubble whose effect did it, or `None` for the player's pops
+    pub source: Option<BubbleType>,
     pub score_change: i32,
 }
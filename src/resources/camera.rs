@@ -0,0 +1,38 @@
+use bevy::prelude::*;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PlayfieldScaling {
+    // Keep the aspect ratio and fill the leftover window space with bars
+    #[default]
+    Letterbox,
+    // Keep the aspect ratio and show whatever is past the edges of the arena
+    Expand,
+    // Squash the arena to fill the window exactly
+    Stretch,
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct Playfield {
+    pub size: Vec2,
+    pub scaling: PlayfieldScaling,
+}
+
+impl Default for Playfield {
+    fn default() -> Self {
+        Self {
+            size: Vec2::new(1280.0, 720.0),
+            scaling: PlayfieldScaling::default(),
+        }
+    }
+}
+
+impl Playfield {
+    // The arena in world space, centered on the origin
+    pub fn bounds(&self) -> Rect {
+        Rect::from_center_size(Vec2::ZERO, self.size)
+    }
+
+    pub fn aspect_ratio(&self) -> f32 {
+        self.size.x / self.size.y
+    }
+}
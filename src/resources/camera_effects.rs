@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+
+use crate::components::bubbles::BubbleType;
+
+#[derive(Resource, Debug, Clone)]
+pub struct CameraEffectsSettings {
+    pub enabled: bool,
+    pub max_offset: f32,
+    pub max_rotation: f32,
+    pub trauma_decay: f32,
+    pub shake_frequency: f32,
+    pub max_zoom_punch: f32,
+    pub zoom_punch_decay: f32,
+    pub zoom_punch_threshold: usize,
+}
+
+impl Default for CameraEffectsSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_offset: 20.0,
+            max_rotation: 0.03,
+            trauma_decay: 1.5,
+            shake_frequency: 25.0,
+            max_zoom_punch: 0.08,
+            zoom_punch_decay: 4.0,
+            zoom_punch_threshold: 30,
+        }
+    }
+}
+
+impl CameraEffectsSettings {
+    pub fn trauma_for(&self, bubble_type: BubbleType) -> f32 {
+        match bubble_type {
+            BubbleType::Mega => 0.4,
+            BubbleType::BlackHole => 0.25,
+            _ => 0.0,
+        }
+    }
+}
+
+#[derive(Resource, Debug, Default, Clone)]
+pub struct CameraEffects {
+    pub trauma: f32,
+    pub zoom_punch: f32,
+    pub elapsed: f32,
+}
+
+impl CameraEffects {
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).min(1.0);
+    }
+
+    pub fn add_zoom_punch(&mut self, amount: f32) {
+        self.zoom_punch = self.zoom_punch.max(amount.min(1.0));
+    }
+
+    // Squaring makes small amounts of trauma barely noticeable while big
+    // explosions still kick hard
+    pub fn shake(&self) -> f32 {
+        self.trauma * self.trauma
+    }
+
+    pub fn clear(&mut self) {
+        self.trauma = 0.0;
+        self.zoom_punch = 0.0;
+    }
+}
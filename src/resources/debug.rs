@@ -0,0 +1,87 @@
+use std::{collections::VecDeque, time::Duration};
+#[cfg(not(target_arch = "wasm32"))]
+use std::{fs::File, io::BufWriter};
+
+use bevy::{prelude::*, utils::{HashMap, Instant}};
+
+use crate::{components::bubbles::BubbleType, systems::bubbles::BubbleSystemSet};
+
+// How many frames the frame time graph looks back over
+pub const FRAME_HISTORY: usize = 240;
+
+#[derive(Resource, Debug, Clone)]
+pub struct DebugTools {
+    pub time_scale: f32,
+    pub frozen: bool,
+    // lets one frame through while frozen
+    pub step_requested: bool,
+    pub spawn_on_click: bool,
+    pub spawn_type: BubbleType,
+    pub show_gizmos: bool,
+    pub show_perf_hud: bool,
+    pub record_csv: bool,
+}
+
+impl Default for DebugTools {
+    fn default() -> Self {
+        Self {
+            time_scale: 1.0,
+            frozen: false,
+            step_requested: false,
+            spawn_on_click: false,
+            spawn_type: BubbleType::Normal,
+            show_gizmos: true,
+            show_perf_hud: true,
+            record_csv: false,
+        }
+    }
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct PerfStats {
+    pub frame: u64,
+    // seconds, oldest first
+    pub frame_times: VecDeque<f32>,
+    // wall time from just before each set ran to just after, so anything
+    // running alongside it gets counted too
+    pub set_times: HashMap<BubbleSystemSet, Duration>,
+    set_starts: HashMap<BubbleSystemSet, Instant>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub csv: Option<BufWriter<File>>,
+}
+
+impl PerfStats {
+    pub fn push_frame_time(&mut self, seconds: f32) {
+        self.frame += 1;
+        self.frame_times.push_back(seconds);
+        while self.frame_times.len() > FRAME_HISTORY {
+            self.frame_times.pop_front();
+        }
+    }
+
+    pub fn last_frame_time(&self) -> f32 {
+        self.frame_times.back().copied().unwrap_or(0.0)
+    }
+
+    pub fn average_fps(&self) -> f32 {
+        let total = self.frame_times.iter().sum::<f32>();
+        if total <= 0.0 {
+            return 0.0;
+        }
+        self.frame_times.len() as f32 / total
+    }
+
+    pub fn start_set(&mut self, set: BubbleSystemSet) {
+        self.set_starts.insert(set, Instant::now());
+    }
+
+    pub fn end_set(&mut self, set: BubbleSystemSet) {
+        if let Some(start) = self.set_starts.remove(&set) {
+            self.set_times.insert(set, start.elapsed());
+        }
+    }
+
+    pub fn set_time(&self, set: BubbleSystemSet) -> Duration {
+        self.set_times.get(&set).copied().unwrap_or_default()
+    }
+}
@@ -0,0 +1,44 @@
+use bevy::prelude::*;
+
+use crate::game_states::GameState;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CameraOwnership {
+    // Spawn a camera and size its viewport to the window
+    #[default]
+    Game,
+    // Use whichever camera the host app tags with `MainCamera`, leaving its
+    // viewport alone, so the game can be drawn into part of the window
+    Host,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputSource {
+    // Read the mouse, touches and keyboard of the primary window
+    #[default]
+    Window,
+    // Leave the devices alone, the host drives `ActionState` and sends
+    // `MouseClickEvent`s itself
+    Host,
+}
+
+// How the game fits into the app it's added to, see `BubbleCollapsePlugins`
+#[derive(Resource, Debug, Clone)]
+pub struct BubbleCollapseConfig {
+    // left to the host when `None`
+    pub clear_color: Option<Color>,
+    pub starting_state: GameState,
+    pub camera: CameraOwnership,
+    pub input: InputSource,
+}
+
+impl Default for BubbleCollapseConfig {
+    fn default() -> Self {
+        Self {
+            clear_color: Some(Color::srgb(0.3, 0.5, 0.8)),
+            starting_state: GameState::default(),
+            camera: CameraOwnership::default(),
+            input: InputSource::default(),
+        }
+    }
+}
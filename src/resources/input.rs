@@ -0,0 +1,211 @@
+use std::collections::{HashMap, HashSet};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum InputAction {
+    Pop,
+    CursorPop,
+    CursorUp,
+    CursorDown,
+    CursorLeft,
+    CursorRight,
+    MenuConfirm,
+    Pause,
+    ToggleDebug,
+    BuyUpgrade(u8),
+    BuyMaxModifier,
+    AutoBuyModifier,
+    CyclePopTool,
+}
+
+impl InputAction {
+    pub const UPGRADE_SLOTS: u8 = 8;
+
+    // Every action in the order it shows up on the options screen
+    pub fn all() -> Vec<InputAction> {
+        let mut actions = vec![
+            InputAction::Pop,
+            InputAction::CursorPop,
+            InputAction::CursorUp,
+            InputAction::CursorDown,
+            InputAction::CursorLeft,
+            InputAction::CursorRight,
+            InputAction::MenuConfirm,
+            InputAction::Pause,
+            InputAction::ToggleDebug,
+        ];
+        actions.extend((1..=Self::UPGRADE_SLOTS).map(InputAction::BuyUpgrade));
+        actions.push(InputAction::BuyMaxModifier);
+        actions.push(InputAction::AutoBuyModifier);
+        actions.push(InputAction::CyclePopTool);
+        actions
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            InputAction::Pop => "Pop".into(),
+            InputAction::CursorPop => "Pop at Cursor".into(),
+            InputAction::CursorUp => "Cursor Up".into(),
+            InputAction::CursorDown => "Cursor Down".into(),
+            InputAction::CursorLeft => "Cursor Left".into(),
+            InputAction::CursorRight => "Cursor Right".into(),
+            InputAction::MenuConfirm => "Menu Confirm".into(),
+            InputAction::Pause => "Pause".into(),
+            InputAction::ToggleDebug => "Toggle Debug".into(),
+            InputAction::BuyUpgrade(slot) => format!("Buy Upgrade {}", slot),
+            InputAction::BuyMaxModifier => "Buy Max (hold)".into(),
+            InputAction::AutoBuyModifier => "Toggle Auto-Buy (hold)".into(),
+            InputAction::CyclePopTool => "Next Pop Tool".into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum InputBinding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    Gamepad(GamepadButton),
+}
+
+impl InputBinding {
+    pub fn name(&self) -> String {
+        match self {
+            InputBinding::Key(key) => format!("{:?}", key),
+            InputBinding::Mouse(button) => format!("Mouse {:?}", button),
+            InputBinding::Gamepad(button) => format!("Pad {:?}", button),
+        }
+    }
+}
+
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActionMap {
+    pub bindings: HashMap<InputAction, Vec<InputBinding>>,
+}
+
+impl Default for ActionMap {
+    fn default() -> Self {
+        let mut map = Self {
+            bindings: HashMap::new(),
+        };
+        map.bind(InputAction::Pop, InputBinding::Mouse(MouseButton::Left));
+        map.bind(InputAction::CursorPop, InputBinding::Key(KeyCode::Space));
+        map.bind(InputAction::CursorPop, InputBinding::Gamepad(GamepadButton::South));
+        map.bind(InputAction::CursorUp, InputBinding::Key(KeyCode::ArrowUp));
+        map.bind(InputAction::CursorUp, InputBinding::Gamepad(GamepadButton::DPadUp));
+        map.bind(InputAction::CursorDown, InputBinding::Key(KeyCode::ArrowDown));
+        map.bind(InputAction::CursorDown, InputBinding::Gamepad(GamepadButton::DPadDown));
+        map.bind(InputAction::CursorLeft, InputBinding::Key(KeyCode::ArrowLeft));
+        map.bind(InputAction::CursorLeft, InputBinding::Gamepad(GamepadButton::DPadLeft));
+        map.bind(InputAction::CursorRight, InputBinding::Key(KeyCode::ArrowRight));
+        map.bind(InputAction::CursorRight, InputBinding::Gamepad(GamepadButton::DPadRight));
+        map.bind(InputAction::MenuConfirm, InputBinding::Key(KeyCode::Enter));
+        map.bind(InputAction::MenuConfirm, InputBinding::Key(KeyCode::Space));
+        map.bind(InputAction::MenuConfirm, InputBinding::Gamepad(GamepadButton::South));
+        map.bind(InputAction::Pause, InputBinding::Key(KeyCode::Escape));
+        map.bind(InputAction::Pause, InputBinding::Gamepad(GamepadButton::Start));
+        map.bind(InputAction::ToggleDebug, InputBinding::Key(KeyCode::Backslash));
+        let digits = [
+            KeyCode::Digit1, KeyCode::Digit2, KeyCode::Digit3, KeyCode::Digit4,
+            KeyCode::Digit5, KeyCode::Digit6, KeyCode::Digit7, KeyCode::Digit8,
+        ];
+        for (slot, key) in (1..=InputAction::UPGRADE_SLOTS).zip(digits) {
+            map.bind(InputAction::BuyUpgrade(slot), InputBinding::Key(key));
+        }
+        map.bind(InputAction::BuyMaxModifier, InputBinding::Key(KeyCode::ShiftLeft));
+        map.bind(InputAction::BuyMaxModifier, InputBinding::Key(KeyCode::ShiftRight));
+        map.bind(InputAction::AutoBuyModifier, InputBinding::Key(KeyCode::ControlLeft));
+        map.bind(InputAction::AutoBuyModifier, InputBinding::Key(KeyCode::ControlRight));
+        map.bind(InputAction::CyclePopTool, InputBinding::Key(KeyCode::Tab));
+        map.bind(InputAction::CyclePopTool, InputBinding::Gamepad(GamepadButton::North));
+        map
+    }
+}
+
+impl ActionMap {
+    pub const SAVE_KEY: &'static str = "bindings";
+
+    pub fn bindings_for(&self, action: InputAction) -> &[InputBinding] {
+        self.bindings.get(&action).map(|bindings| bindings.as_slice()).unwrap_or(&[])
+    }
+
+    pub fn bind(&mut self, action: InputAction, binding: InputBinding) {
+        let bindings = self.bindings.entry(action).or_default();
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+    }
+
+    // Replaces the binding in `slot`, or adds a new one if the slot doesn't exist yet
+    pub fn rebind(&mut self, action: InputAction, slot: usize, binding: InputBinding) {
+        let bindings = self.bindings.entry(action).or_default();
+        bindings.retain(|existing| *existing != binding);
+        if slot < bindings.len() {
+            bindings[slot] = binding;
+        } else {
+            bindings.push(binding);
+        }
+    }
+
+    // Saved maps from older versions don't know about newer actions, so those get
+    // their default bindings. Actions the player deliberately unbound are left alone
+    pub fn add_missing_defaults(&mut self) {
+        let defaults = Self::default();
+        for (action, bindings) in defaults.bindings {
+            self.bindings.entry(action).or_insert(bindings);
+        }
+    }
+
+    pub fn unbind(&mut self, action: InputAction, slot: usize) {
+        if let Some(bindings) = self.bindings.get_mut(&action) {
+            if slot < bindings.len() {
+                bindings.remove(slot);
+            }
+        }
+    }
+}
+
+#[derive(Resource, Debug, Default, Clone)]
+pub struct ActionState {
+    pressed: HashSet<InputAction>,
+    just_pressed: HashSet<InputAction>,
+    just_released: HashSet<InputAction>,
+}
+
+impl ActionState {
+    pub fn pressed(&self, action: InputAction) -> bool {
+        self.pressed.contains(&action)
+    }
+
+    pub fn just_pressed(&self, action: InputAction) -> bool {
+        self.just_pressed.contains(&action)
+    }
+
+    pub fn just_released(&self, action: InputAction) -> bool {
+        self.just_released.contains(&action)
+    }
+
+    pub fn clear(&mut self) {
+        self.pressed.clear();
+        self.just_pressed.clear();
+        self.just_released.clear();
+    }
+
+    pub fn record(&mut self, action: InputAction, pressed: bool, just_pressed: bool, just_released: bool) {
+        if pressed {
+            self.pressed.insert(action);
+        }
+        if just_pressed {
+            self.just_pressed.insert(action);
+        }
+        if just_released {
+            self.just_released.insert(action);
+        }
+    }
+}
+
+#[derive(Resource, Debug, Default, Clone)]
+pub struct RebindState {
+    pub waiting_for: Option<(InputAction, usize)>,
+}
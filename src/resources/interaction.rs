@@ -3,5 +3,95 @@ use bevy::prelude::*;
 #[derive(Event)]
 pub struct MouseClickEvent {
     pub position: Vec2,
+    // Where a swept pop started, everything between here and `position` gets
+    // popped. Plain clicks start and end in the same place
+    pub path_start: Vec2,
     pub window_position: Vec2,
+    // Extra reach added to every bubble's collider, used to make finger taps
+    // more forgiving than a mouse pointer
+    pub radius: f32,
+}
+
+// What the UI is covering this frame, so gameplay can ignore presses the UI
+// already handled. Rects are in logical window coordinates
+#[derive(Resource, Debug, Default, Clone)]
+pub struct PointerCapture {
+    pub hovering_ui: bool,
+    pub egui_wants_pointer: bool,
+    pub ui_rects: Vec<Rect>,
+}
+
+impl PointerCapture {
+    pub fn blocks(&self, window_position: Vec2) -> bool {
+        self.egui_wants_pointer || self.ui_rects.iter().any(|rect| rect.contains(window_position))
+    }
+
+    pub fn blocks_mouse(&self, window_position: Vec2) -> bool {
+        self.hovering_ui || self.blocks(window_position)
+    }
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct VirtualCursor {
+    pub active: bool,
+    pub position: Vec2,
+    pub speed: f32,
+    pub base_speed: f32,
+    pub max_speed: f32,
+    pub acceleration: f32,
+    pub dead_zone: f32,
+}
+
+impl Default for VirtualCursor {
+    fn default() -> Self {
+        Self {
+            active: false,
+            position: Vec2::ZERO,
+            speed: 0.0,
+            base_speed: 250.0,
+            max_speed: 1200.0,
+            acceleration: 1500.0,
+            dead_zone: 0.15,
+        }
+    }
+}
+
+impl VirtualCursor {
+    // Speeds up the longer the stick or keys are held, so small corrections
+    // stay precise but crossing the screen doesn't take forever
+    pub fn accelerate(&mut self, delta: f32) {
+        self.speed = (self.speed.max(self.base_speed) + self.acceleration * delta).min(self.max_speed);
+    }
+
+    pub fn stop(&mut self) {
+        self.speed = 0.0;
+    }
+}
+
+#[derive(Resource, Debug, Default, Clone)]
+pub struct MenuFocus {
+    pub active: bool,
+    pub focused: Option<Entity>,
+    pub pressed: Option<Entity>,
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct TouchSettings {
+    pub enlarge_taps: bool,
+    pub tap_radius: f32,
+}
+
+impl Default for TouchSettings {
+    fn default() -> Self {
+        Self {
+            enlarge_taps: true,
+            tap_radius: 12.0,
+        }
+    }
+}
+
+impl TouchSettings {
+    pub fn effective_tap_radius(&self) -> f32 {
+        if self.enlarge_taps { self.tap_radius } else { 0.0 }
+    }
 }
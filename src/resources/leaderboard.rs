@@ -0,0 +1,90 @@
+use std::cmp::Reverse;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::resources::run::GameMode;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    pub name: String,
+    pub mode: GameMode,
+    pub score: i32,
+    pub longest_chain: u32,
+    // seconds since the unix epoch
+    pub date: u64,
+    pub seed: u64,
+    // persistence key of the run's replay, if it was recorded
+    pub replay: Option<String>,
+}
+
+// The best runs in each mode, kept sorted by score
+#[derive(Resource, Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Leaderboard {
+    pub entries: Vec<LeaderboardEntry>,
+    // filled in for the next name entry
+    pub last_name: String,
+}
+
+impl Leaderboard {
+    pub const SAVE_KEY: &'static str = "leaderboard";
+    pub const SIZE: usize = 10;
+
+    pub fn entries_for(&self, mode: GameMode) -> impl Iterator<Item = &LeaderboardEntry> {
+        self.entries.iter().filter(move |entry| entry.mode == mode)
+    }
+
+    pub fn qualifies(&self, mode: GameMode, score: i32) -> bool {
+        score > 0 && (self.entries_for(mode).count() < Self::SIZE
+            || self.entries_for(mode).any(|entry| score > entry.score))
+    }
+
+    pub fn insert(&mut self, entry: LeaderboardEntry) {
+        let mode = entry.mode;
+        self.last_name = entry.name.clone();
+        self.entries.push(entry);
+        self.entries.sort_by_key(|entry| Reverse(entry.score));
+        // only the lowest entry of the same mode falls off
+        if self.entries_for(mode).count() > Self::SIZE {
+            if let Some(index) = self.entries.iter().rposition(|entry| entry.mode == mode) {
+                self.entries.remove(index);
+            }
+        }
+    }
+}
+
+// A finished run that made the board, waiting for the player's name
+#[derive(Resource, Debug, Clone)]
+pub struct PendingLeaderboardEntry {
+    pub entry: LeaderboardEntry,
+    pub recorded: bool,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LeaderboardSort {
+    #[default]
+    Score,
+    Chain,
+    Date,
+}
+
+impl LeaderboardSort {
+    pub fn all() -> [LeaderboardSort; 3] {
+        [LeaderboardSort::Score, LeaderboardSort::Chain, LeaderboardSort::Date]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            LeaderboardSort::Score => "Score",
+            LeaderboardSort::Chain => "Chain",
+            LeaderboardSort::Date => "Date",
+        }
+    }
+}
+
+// What the leaderboard screen is showing
+#[derive(Resource, Debug, Default, Clone)]
+pub struct LeaderboardView {
+    pub mode: GameMode,
+    pub sort: LeaderboardSort,
+}
@@ -0,0 +1,51 @@
+use bevy::prelude::*;
+
+#[derive(Resource, Debug, Clone)]
+pub struct MusicSettings {
+    pub volume: f32,
+    // how long a full fade between the menu and play tracks takes
+    pub crossfade_secs: f32,
+    pub layer_fade_secs: f32,
+    // volume multiplier while the game is paused
+    pub pause_duck: f32,
+    // on-screen bubbles and chain length that each count as one intensity level
+    pub bubbles_per_level: f32,
+    pub chain_per_level: f32,
+}
+
+impl Default for MusicSettings {
+    fn default() -> Self {
+        Self {
+            volume: 0.6,
+            crossfade_secs: 1.5,
+            layer_fade_secs: 2.0,
+            pause_duck: 0.3,
+            bubbles_per_level: 80.0,
+            chain_per_level: 25.0,
+        }
+    }
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct MusicMix {
+    // how busy the playfield is, smoothed so layers don't flicker in and out
+    // the moment a chain ends
+    pub intensity: f32,
+    pub duck: f32,
+}
+
+impl Default for MusicMix {
+    fn default() -> Self {
+        Self {
+            intensity: 0.0,
+            duck: 1.0,
+        }
+    }
+}
+
+impl MusicMix {
+    // Layer `n` fades in as the intensity goes from n-1 to n
+    pub fn layer_target(&self, layer: u8) -> f32 {
+        (self.intensity - (layer as f32 - 1.0)).clamp(0.0, 1.0)
+    }
+}
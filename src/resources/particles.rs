@@ -0,0 +1,77 @@
+use bevy::prelude::*;
+use bevy_rand::prelude::WyRand;
+use rand_core::SeedableRng;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParticleQuality {
+    Off,
+    Low,
+    #[default]
+    Medium,
+    High,
+}
+
+impl ParticleQuality {
+    pub fn max_particles(&self) -> usize {
+        match self {
+            ParticleQuality::Off => 0,
+            ParticleQuality::Low => 256,
+            ParticleQuality::Medium => 1024,
+            ParticleQuality::High => 4096,
+        }
+    }
+
+    // Multiplier applied to every burst size and emission rate
+    pub fn density(&self) -> f32 {
+        match self {
+            ParticleQuality::Off => 0.0,
+            ParticleQuality::Low => 0.4,
+            ParticleQuality::Medium => 1.0,
+            ParticleQuality::High => 2.0,
+        }
+    }
+}
+
+#[derive(Resource, Debug, Default, Clone)]
+pub struct ParticleSettings {
+    pub quality: ParticleQuality,
+}
+
+#[derive(Resource, Debug, Default, Clone)]
+pub struct ParticlePool {
+    pub entities: Vec<Entity>,
+    next: usize,
+}
+
+impl ParticlePool {
+    // Hands out pooled particles round-robin, so when the pool is exhausted the
+    // oldest particle gets recycled instead of a new entity being spawned
+    pub fn next_entity(&mut self) -> Option<Entity> {
+        if self.entities.is_empty() {
+            return None;
+        }
+        let entity = self.entities[self.next];
+        self.next = (self.next + 1) % self.entities.len();
+        Some(entity)
+    }
+
+    pub fn reset(&mut self) {
+        self.entities.clear();
+        self.next = 0;
+    }
+}
+
+// Particles draw from their own rng rather than the gameplay one, so the
+// quality setting can't change how a run plays out
+#[derive(Resource, Debug, Clone)]
+pub struct ParticleRandom {
+    pub rng: WyRand,
+}
+
+impl Default for ParticleRandom {
+    fn default() -> Self {
+        Self {
+            rng: WyRand::seed_from_u64(0),
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PopTool {
+    #[default]
+    Click,
+    Sweep,
+    Charge,
+    Area,
+}
+
+impl PopTool {
+    pub fn all() -> [PopTool; 4] {
+        [PopTool::Click, PopTool::Sweep, PopTool::Charge, PopTool::Area]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PopTool::Click => "Click",
+            PopTool::Sweep => "Sweep Tool",
+            PopTool::Charge => "Charge Tool",
+            PopTool::Area => "Area Pop Tool",
+        }
+    }
+}
+
+#[derive(Resource, Debug, Default, Clone)]
+pub struct PopTools {
+    pub selected: PopTool,
+}
+
+// Pops everything along the cursor's path while the button is held. The sweep
+// runs on energy measured in pixels travelled, so it can clear a line through a
+// crowd but can't just be dragged around the whole screen forever
+#[derive(Resource, Debug, Clone)]
+pub struct SweepTool {
+    pub width: f32,
+    pub energy: f32,
+    pub max_energy: f32,
+    pub recharge_rate: f32,
+    // recharging only starts once the button has been let go for a moment
+    pub recharge_delay: Timer,
+    pub last_position: Option<Vec2>,
+}
+
+impl Default for SweepTool {
+    fn default() -> Self {
+        Self {
+            width: 16.0,
+            energy: 1200.0,
+            max_energy: 1200.0,
+            recharge_rate: 400.0,
+            recharge_delay: Timer::from_seconds(0.5, TimerMode::Once),
+            last_position: None,
+        }
+    }
+}
+
+// Pops on release instead of on press, with a radius that grows the longer the
+// button was held. The growth eases out, so a short hold is already useful but
+// a full charge still takes patience
+#[derive(Resource, Debug, Clone)]
+pub struct ChargeTool {
+    pub min_radius: f32,
+    pub max_radius: f32,
+    pub charge_time: f32,
+    pub charge: f32,
+    pub charging: bool,
+}
+
+impl Default for ChargeTool {
+    fn default() -> Self {
+        Self {
+            min_radius: 0.0,
+            max_radius: 90.0,
+            charge_time: 1.2,
+            charge: 0.0,
+            charging: false,
+        }
+    }
+}
+
+impl ChargeTool {
+    pub fn radius(&self) -> f32 {
+        let eased = 1.0 - (1.0 - self.charge).powi(2);
+        self.min_radius + (self.max_radius - self.min_radius) * eased
+    }
+}
+
+// One big pop, then a long cooldown. While it's cooling down clicks fall back
+// to regular single pops
+#[derive(Resource, Debug, Clone)]
+pub struct AreaPopTool {
+    pub radius: f32,
+    pub cooldown: Timer,
+}
+
+impl Default for AreaPopTool {
+    fn default() -> Self {
+        let mut cooldown = Timer::from_seconds(8.0, TimerMode::Once);
+        // ready straight away after being unlocked
+        cooldown.tick(cooldown.duration());
+        Self {
+            radius: 140.0,
+            cooldown,
+        }
+    }
+}
+
+impl AreaPopTool {
+    pub fn is_ready(&self) -> bool {
+        self.cooldown.finished()
+    }
+}
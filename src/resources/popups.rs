@@ -0,0 +1,51 @@
+use bevy::prelude::*;
+
+#[derive(Resource, Debug, Clone)]
+pub struct ScorePopupPool {
+    pub entities: Vec<Entity>,
+    pub pool_size: usize,
+    pub spawns_per_second: f32,
+    pub max_burst: f32,
+    pub merge_radius: f32,
+    pub lifetime: f32,
+    pub rise_distance: f32,
+    pub chain_threshold: u32,
+    spawn_budget: f32,
+}
+
+impl Default for ScorePopupPool {
+    fn default() -> Self {
+        Self {
+            entities: vec![],
+            pool_size: 64,
+            spawns_per_second: 30.0,
+            max_burst: 10.0,
+            merge_radius: 80.0,
+            lifetime: 0.8,
+            rise_distance: 60.0,
+            chain_threshold: 10,
+            spawn_budget: 0.0,
+        }
+    }
+}
+
+impl ScorePopupPool {
+    pub fn refill(&mut self, delta: f32) {
+        self.spawn_budget = (self.spawn_budget + self.spawns_per_second * delta).min(self.max_burst);
+    }
+
+    // Returns whether a new popup may be shown, consuming budget if so
+    pub fn try_spawn(&mut self) -> bool {
+        if self.spawn_budget >= 1.0 {
+            self.spawn_budget -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.entities.clear();
+        self.spawn_budget = self.max_burst;
+    }
+}
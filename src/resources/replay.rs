@@ -0,0 +1,82 @@
+use bevy::{prelude::*, utils::HashMap};
+use serde::{Deserialize, Serialize};
+
+use crate::{resources::{interaction::MouseClickEvent, run::GameMode}, systems::ui::upgrades_menu::UpgradesMenuAction};
+
+// Replays store what went into a run rather than what happened in it: the
+// seed, how long every frame took, and the pops and purchases made on it.
+// Playing those back through the same systems gets the same run, as long as
+// the game itself hasn't changed since the recording
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Replay {
+    pub seed: u64,
+    pub mode: GameMode,
+    pub frames: Vec<ReplayFrame>,
+}
+
+impl Replay {
+    pub const LAST_RUN_KEY: &'static str = "replays/last";
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ReplayFrame {
+    // real seconds the frame took
+    pub delta: f64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pops: Vec<ReplayPop>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub purchases: Vec<(UpgradesMenuAction, u32)>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ReplayPop {
+    pub position: Vec2,
+    pub path_start: Vec2,
+    pub window_position: Vec2,
+    pub radius: f32,
+}
+
+impl ReplayPop {
+    pub fn from_event(event: &MouseClickEvent) -> Self {
+        Self {
+            position: event.position,
+            path_start: event.path_start,
+            window_position: event.window_position,
+            radius: event.radius,
+        }
+    }
+
+    pub fn to_event(&self) -> MouseClickEvent {
+        MouseClickEvent {
+            position: self.position,
+            path_start: self.path_start,
+            window_position: self.window_position,
+            radius: self.radius,
+        }
+    }
+}
+
+#[derive(Resource, Debug, Default, Clone)]
+pub struct ReplayRecorder {
+    pub replay: Replay,
+    // purchase counts as of the last recorded frame, to tell what got bought since
+    pub purchases: HashMap<UpgradesMenuAction, u32>,
+}
+
+// Marks that the run which just ended was recorded to `Replay::LAST_RUN_KEY`,
+// which resumed runs aren't
+#[derive(Resource, Debug, Default)]
+pub struct RecordedReplay;
+
+#[derive(Resource, Debug, Clone)]
+pub struct ReplayPlayback {
+    pub replay: Replay,
+    pub frame: usize,
+    pub exit_when_done: bool,
+}
+
+impl ReplayPlayback {
+    pub fn current_frame(&self) -> Option<&ReplayFrame> {
+        self.replay.frames.get(self.frame)
+    }
+}
@@ -0,0 +1,72 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GameMode {
+    #[default]
+    Endless,
+    // the same game, but the run ends after a few minutes
+    TimeAttack,
+}
+
+impl GameMode {
+    pub fn all() -> [GameMode; 2] {
+        [GameMode::Endless, GameMode::TimeAttack]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameMode::Endless => "Endless",
+            GameMode::TimeAttack => "Time Attack",
+        }
+    }
+
+    // What the mode is called on the command line
+    pub fn id(&self) -> &'static str {
+        match self {
+            GameMode::Endless => "endless",
+            GameMode::TimeAttack => "time-attack",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<GameMode> {
+        Self::all().into_iter().find(|mode| mode.id() == id)
+    }
+
+    pub fn time_limit(&self) -> Option<f32> {
+        match self {
+            GameMode::Endless => None,
+            GameMode::TimeAttack => Some(180.0),
+        }
+    }
+}
+
+// Every run reseeds the global rng, so a run can be reproduced from its seed
+#[derive(Resource, Debug, Default, Clone)]
+pub struct RunSeed {
+    // used for the next run instead of a random seed, then cleared
+    pub next: Option<u64>,
+    pub current: u64,
+}
+
+#[derive(Resource, Debug, Default, Clone)]
+pub struct RunTimer {
+    pub elapsed: f32,
+}
+
+impl RunTimer {
+    pub fn remaining(&self, mode: GameMode) -> Option<f32> {
+        mode.time_limit().map(|limit| (limit - self.elapsed).max(0.0))
+    }
+}
+
+// Set once the debug tools have changed the current run, which then can't
+// count towards anything the player keeps
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct RunTainted(pub bool);
+
+// Quits after a set number of frames, for scripted runs
+#[derive(Resource, Debug, Clone)]
+pub struct TickLimit {
+    pub remaining: u32,
+}
@@ -0,0 +1,91 @@
+use bevy::prelude::*;
+use bevy_rand::prelude::{GlobalEntropy, WyRand};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    components::{bubbles::*, physics::Collider},
+    resources::{run::GameMode, stats::RunStats},
+    systems::ui::upgrades_menu::UpgradesMenuAction,
+    util::ActionTimer,
+};
+
+// Everything needed to pick a run back up where it was left, down to the rng,
+// so it carries on the same way it would have
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedRun {
+    pub mode: GameMode,
+    pub seed: u64,
+    pub elapsed: f32,
+    pub score: i32,
+    pub upgrades: SavedUpgrades,
+    pub chances: Vec<(BubbleType, f32)>,
+    pub spawn_timer: ActionTimer,
+    pub random: GlobalEntropy<WyRand>,
+    pub bubbles: Vec<SavedBubble>,
+    pub shockwaves: Vec<SavedShockwave>,
+    pub black_holes: Vec<SavedBlackHole>,
+    pub beams: Vec<SavedBeam>,
+    pub scatter_shots: Vec<SavedScatterShot>,
+    #[serde(default)]
+    pub stats: RunStats,
+    #[serde(default)]
+    pub tainted: bool,
+}
+
+impl SavedRun {
+    pub const SAVE_KEY: &'static str = "run";
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct SavedUpgrades {
+    pub unlocked: Vec<UpgradesMenuAction>,
+    pub costs: Vec<(UpgradesMenuAction, u32)>,
+    pub auto_buy: Vec<UpgradesMenuAction>,
+    pub purchases: Vec<(UpgradesMenuAction, u32)>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedBubble {
+    pub transform: Transform,
+    pub bubble: Bubble,
+    pub velocity: Vec2,
+    pub collider: Collider,
+    pub color: Color,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedShockwave {
+    pub transform: Transform,
+    pub timed_effect: TimedEffect,
+    pub shockwave: BubbleShockwave,
+    pub collider: Collider,
+    pub color: Color,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedBlackHole {
+    pub transform: Transform,
+    pub timed_effect: TimedEffect,
+    pub black_hole: BubbleBlackHole,
+    pub collider: Collider,
+    pub color: Color,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedBeam {
+    pub transform: Transform,
+    pub timed_effect: TimedEffect,
+    pub beam: BubbleBeam,
+    pub color: Color,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedScatterShot {
+    pub transform: Transform,
+    pub action_timer: ActionTimer,
+    pub spawner: BubbleScatterShotSpawner,
+}
+
+// The run picked from the main menu, restored once the new run has been set up
+#[derive(Resource, Debug, Clone)]
+pub struct ResumeRun(pub SavedRun);
@@ -1,6 +1,116 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::HashMap};
+use serde::{Deserialize, Serialize};
+
+use crate::{components::bubbles::BubbleType, resources::run::GameMode, systems::ui::upgrades_menu::UpgradesMenuAction};
 
 #[derive(Resource, Debug, Default)]
 pub struct GameStats {
     pub score: i32,
 }
+
+// A chain is every collapse that follows closely on the one before it, so a
+// cascade set off by a single click counts as one long chain
+#[derive(Resource, Debug, Clone)]
+pub struct Chain {
+    pub length: u32,
+    pub timer: Timer,
+}
+
+impl Default for Chain {
+    fn default() -> Self {
+        Self {
+            length: 0,
+            timer: Timer::from_seconds(1.0, TimerMode::Once),
+        }
+    }
+}
+
+impl Chain {
+    pub fn extend(&mut self) {
+        self.length += 1;
+        self.timer.reset();
+    }
+
+    // Returns the final length of the chain if it just ended
+    pub fn tick(&mut self, delta: std::time::Duration) -> Option<u32> {
+        if self.length == 0 {
+            return None;
+        }
+        self.timer.tick(delta);
+        if !self.timer.finished() {
+            return None;
+        }
+        let length = self.length;
+        self.length = 0;
+        Some(length)
+    }
+}
+
+// The breakdown of a single run, shown once it's over
+#[derive(Resource, Debug, Default, Clone, Serialize, Deserialize)]
+pub struct RunStats {
+    pub popped: HashMap<BubbleType, u32>,
+    pub user_pops: u32,
+    pub effect_pops: u32,
+    pub longest_chain: u32,
+    pub peak_pops_per_second: u32,
+    // score from the pops each kind of bubble's effect caused
+    pub score_by_source: HashMap<BubbleType, i32>,
+    pub time_alive: f32,
+    pub upgrades_bought: HashMap<UpgradesMenuAction, u32>,
+    // pops in each whole second of the run, oldest first
+    pub pops_per_second: Vec<u32>,
+    #[serde(skip)]
+    second_pops: u32,
+    #[serde(skip)]
+    second_elapsed: f32,
+}
+
+impl RunStats {
+    pub fn record_pops(&mut self, pops: u32, delta: f32) {
+        self.second_pops += pops;
+        self.second_elapsed += delta;
+        while self.second_elapsed >= 1.0 {
+            self.second_elapsed -= 1.0;
+            self.peak_pops_per_second = self.peak_pops_per_second.max(self.second_pops);
+            self.pops_per_second.push(self.second_pops);
+            self.second_pops = 0;
+        }
+    }
+
+    pub fn total_popped(&self) -> u32 {
+        self.popped.values().sum()
+    }
+
+    pub fn total_upgrades_bought(&self) -> u32 {
+        self.upgrades_bought.values().sum()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunRecord {
+    // seconds since the unix epoch
+    pub date: u64,
+    pub mode: GameMode,
+    pub seed: u64,
+    pub score: i32,
+    pub stats: RunStats,
+}
+
+// Every finished run, newest last
+#[derive(Resource, Debug, Default, Clone, Serialize, Deserialize)]
+pub struct RunHistory {
+    pub runs: Vec<RunRecord>,
+}
+
+impl RunHistory {
+    pub const SAVE_KEY: &'static str = "history";
+    // older runs get dropped past this many
+    pub const MAX_RUNS: usize = 100;
+
+    pub fn push(&mut self, record: RunRecord) {
+        self.runs.push(record);
+        let excess = self.runs.len().saturating_sub(Self::MAX_RUNS);
+        self.runs.drain(..excess);
+    }
+}
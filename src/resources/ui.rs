@@ -1,11 +1,13 @@
-use bevy::{prelude::*, utils::HashMap};
+use bevy::{prelude::*, utils::{HashMap, HashSet}};
 
-use crate::{components::bubbles::BubbleType, systems::ui::upgrades_menu::UpgradesMenuAction};
+use crate::{components::bubbles::BubbleType, resources::pop_tools::PopTool, systems::ui::upgrades_menu::UpgradesMenuAction};
 
 #[derive(Resource, Debug)]
 pub struct UpgradesMenuInfo {
     pub unlocked: Vec<UpgradesMenuAction>,
     pub costs: HashMap<UpgradesMenuAction, u32>,
+    pub auto_buy: HashSet<UpgradesMenuAction>,
+    pub purchases: HashMap<UpgradesMenuAction, u32>,
 }
 
 impl Default for UpgradesMenuInfo {
@@ -19,8 +21,13 @@ impl Default for UpgradesMenuInfo {
                 map.insert(UpgradesMenuAction::IncreaseChance(BubbleType::ScatterShot), 150);
                 map.insert(UpgradesMenuAction::IncreaseChance(BubbleType::BlackHole), 1000);
                 map.insert(UpgradesMenuAction::IncreaseChance(BubbleType::Mega), 10000);
+                map.insert(UpgradesMenuAction::UnlockTool(PopTool::Sweep), 300);
+                map.insert(UpgradesMenuAction::UnlockTool(PopTool::Charge), 2000);
+                map.insert(UpgradesMenuAction::UnlockTool(PopTool::Area), 6000);
                 map
             },
+            auto_buy: HashSet::default(),
+            purchases: HashMap::default(),
         }
     }
 }
@@ -33,6 +40,28 @@ impl UpgradesMenuInfo {
     pub fn unlock(&mut self, action: UpgradesMenuAction) {
         self.unlocked.push(action);
     }
+
+    pub fn purchase_count(&self, action: UpgradesMenuAction) -> u32 {
+        *self.purchases.get(&action).unwrap_or(&0)
+    }
+
+    pub fn record_purchase(&mut self, action: UpgradesMenuAction) {
+        *self.purchases.entry(action).or_default() += 1;
+    }
+
+    pub fn owns_tool(&self, tool: PopTool) -> bool {
+        tool == PopTool::Click || self.purchase_count(UpgradesMenuAction::UnlockTool(tool)) > 0
+    }
+
+    pub fn is_auto_buying(&self, action: UpgradesMenuAction) -> bool {
+        self.auto_buy.contains(&action)
+    }
+
+    pub fn toggle_auto_buy(&mut self, action: UpgradesMenuAction) {
+        if !self.auto_buy.remove(&action) {
+            self.auto_buy.insert(action);
+        }
+    }
 }
 
 #[derive(Event, Debug)]
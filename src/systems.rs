@@ -6,3 +6,17 @@ pub mod ui;
 pub mod stats;
 pub mod cache;
 pub mod audio;
+pub mod popups;
+pub mod particles;
+pub mod camera_effects;
+pub mod input;
+pub mod pop_tools;
+pub mod music;
+pub mod debug;
+pub mod benchmark;
+pub mod run;
+pub mod replay;
+pub mod embedding;
+pub mod save;
+pub mod achievements;
+pub mod leaderboard;
@@ -0,0 +1,108 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::{
+    persistence,
+    resources::{achievements::*, bubbles::BubbleCollapsedEvent, run::GameMode, stats::{GameStats, RunStats}, ui::{UpgradeChangedEvent, UpgradesMenuInfo}},
+    util,
+};
+
+// Everything the conditions are checked against
+#[derive(SystemParam)]
+pub struct RunProgress<'w> {
+    mode: Res<'w, GameMode>,
+    game_stats: Res<'w, GameStats>,
+    run_stats: Res<'w, RunStats>,
+    upgrades: Res<'w, UpgradesMenuInfo>,
+}
+
+impl RunProgress<'_> {
+    fn context(&self, run_over: bool) -> AchievementContext<'_> {
+        AchievementContext {
+            mode: *self.mode,
+            score: self.game_stats.score,
+            stats: &self.run_stats,
+            upgrades: &self.upgrades,
+            run_over,
+        }
+    }
+}
+
+pub fn load_achievements(
+    mut commands: Commands,
+) {
+    if let Some(unlocked) = persistence::load::<UnlockedAchievements>(UnlockedAchievements::SAVE_KEY) {
+        commands.insert_resource(unlocked);
+    }
+}
+
+pub fn save_achievements(
+    unlocked: Res<UnlockedAchievements>,
+) {
+    if unlocked.is_changed() && !unlocked.is_added() {
+        persistence::save(UnlockedAchievements::SAVE_KEY, unlocked.as_ref());
+    }
+}
+
+// Conditions only change when something gets popped or bought, so they're
+// only checked on frames where that happened
+pub fn check_achievements(
+    progress: RunProgress,
+    mut unlocked: ResMut<UnlockedAchievements>,
+    mut bubble_collapse_event: EventReader<BubbleCollapsedEvent>,
+    mut upgrade_changed_event: EventReader<UpgradeChangedEvent>,
+    mut unlock_events: EventWriter<AchievementUnlockedEvent>,
+) {
+    let popped = bubble_collapse_event.read().count() > 0;
+    let bought = upgrade_changed_event.read().count() > 0;
+    if !popped && !bought {
+        return;
+    }
+    unlock_met(&progress.context(false), &mut unlocked, &mut unlock_events);
+}
+
+pub fn check_run_over_achievements(
+    progress: RunProgress,
+    mut unlocked: ResMut<UnlockedAchievements>,
+    mut unlock_events: EventWriter<AchievementUnlockedEvent>,
+) {
+    unlock_met(&progress.context(true), &mut unlocked, &mut unlock_events);
+}
+
+fn unlock_met(
+    context: &AchievementContext,
+    unlocked: &mut UnlockedAchievements,
+    unlock_events: &mut EventWriter<AchievementUnlockedEvent>,
+) {
+    for achievement in Achievement::all() {
+        if !unlocked.is_unlocked(achievement) && achievement.is_met(context) {
+            unlocked.unlock(achievement, util::unix_time());
+            unlock_events.send(AchievementUnlockedEvent { achievement });
+        }
+    }
+}
+
+pub fn queue_achievement_toasts(
+    mut toasts: ResMut<AchievementToasts>,
+    mut unlock_events: EventReader<AchievementUnlockedEvent>,
+) {
+    for event in unlock_events.read() {
+        if toasts.queue.is_empty() {
+            toasts.timer = Timer::from_seconds(AchievementToasts::DURATION, TimerMode::Once);
+        }
+        toasts.queue.push_back(event.achievement);
+    }
+}
+
+pub fn advance_achievement_toasts(
+    time: Res<Time<Real>>,
+    mut toasts: ResMut<AchievementToasts>,
+) {
+    if toasts.queue.is_empty() {
+        return;
+    }
+    toasts.timer.tick(time.delta());
+    if toasts.timer.finished() {
+        toasts.queue.pop_front();
+        toasts.timer = Timer::from_seconds(AchievementToasts::DURATION, TimerMode::Once);
+    }
+}
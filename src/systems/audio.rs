@@ -1,16 +1,143 @@
-use bevy::prelude::*;
+use bevy::{audio::{SpatialScale, Volume}, prelude::*};
 
-use crate::{components::audio::AudioLimitRemovalFlag, resources::audio::AudioLimiter};
+use crate::{components::{audio::{AudioBus, AudioLimitRemovalFlag}, camera::MainCamera}, persistence, resources::{audio::{AudioLimiter, AudioVolumes, ComboPitch, PitchSettings, StereoSettings}, camera::Playfield, stats::Chain}};
+
+// Rodio pans by making the ear further from the sound louder rather than the
+// closer one, so the ears are swapped to make left sound left
+const EAR_OFFSET: Vec3 = Vec3::new(640.0, 0.0, 0.0);
+// Rodio also never pans further than halving the far side, which is quieter
+// overall than a centered mono sound, so panned sounds get this much louder
+const PANNED_LOUDNESS: f32 = 4.0 / 3.0;
 
 pub fn decrease_limiter_on_removal(
     trigger: Trigger<OnRemove, AudioLimitRemovalFlag>,
     mut audio_limiter: ResMut<AudioLimiter>,
-    query: Query<&AudioPlayer>,
 ) {
-    let entity = trigger.entity();
-    if let Ok(player) = query.get(entity) {
-        audio_limiter.decrease_count(player.0.clone());
-    } else {
-        warn!("Entity {:?} does not have an AudioPlayer component", entity);
+    audio_limiter.release(trigger.entity());
+}
+
+pub fn reset_audio_spawn_budget(
+    mut audio_limiter: ResMut<AudioLimiter>,
+) {
+    audio_limiter.new_frame();
+}
+
+pub fn load_audio_volumes(
+    mut commands: Commands,
+) {
+    if let Some(volumes) = persistence::load::<AudioVolumes>(AudioVolumes::SAVE_KEY) {
+        commands.insert_resource(volumes);
+    }
+}
+
+pub fn save_audio_volumes(
+    volumes: Res<AudioVolumes>,
+) {
+    if volumes.is_changed() && !volumes.is_added() {
+        persistence::save(AudioVolumes::SAVE_KEY, volumes.as_ref());
+    }
+}
+
+// New sounds haven't got a sink yet, so their category volume goes into the
+// settings the sink gets created from
+pub fn apply_volume_to_new_sounds(
+    volumes: Res<AudioVolumes>,
+    mut sound_query: Query<(&AudioBus, &mut PlaybackSettings), Added<AudioBus>>,
+) {
+    for (bus, mut playback_settings) in &mut sound_query {
+        playback_settings.volume = Volume::new(bus.volume * volumes.volume_for(bus.category));
+    }
+}
+
+pub fn apply_volume_to_playing_sounds(
+    volumes: Res<AudioVolumes>,
+    sound_query: Query<(&AudioBus, &AudioSink)>,
+    spatial_sound_query: Query<(&AudioBus, &SpatialAudioSink)>,
+) {
+    if !volumes.is_changed() {
+        return;
+    }
+    for (bus, sink) in &sound_query {
+        sink.set_volume(bus.volume * volumes.volume_for(bus.category));
+    }
+    for (bus, sink) in &spatial_sound_query {
+        sink.set_volume(bus.volume * volumes.volume_for(bus.category));
+    }
+}
+
+pub fn load_stereo_settings(
+    mut commands: Commands,
+) {
+    if let Some(settings) = persistence::load::<StereoSettings>(StereoSettings::SAVE_KEY) {
+        commands.insert_resource(settings);
+    }
+}
+
+pub fn save_stereo_settings(
+    settings: Res<StereoSettings>,
+) {
+    if settings.is_changed() && !settings.is_added() {
+        persistence::save(StereoSettings::SAVE_KEY, settings.as_ref());
+    }
+}
+
+pub fn load_pitch_settings(
+    mut commands: Commands,
+) {
+    if let Some(settings) = persistence::load::<PitchSettings>(PitchSettings::SAVE_KEY) {
+        commands.insert_resource(settings);
+    }
+}
+
+pub fn save_pitch_settings(
+    settings: Res<PitchSettings>,
+) {
+    if settings.is_changed() && !settings.is_added() {
+        persistence::save(PitchSettings::SAVE_KEY, settings.as_ref());
+    }
+}
+
+// The next chain starts again from the root note
+pub fn reset_combo_pitch(
+    chain: Res<Chain>,
+    mut combo_pitch: ResMut<ComboPitch>,
+) {
+    if chain.length == 0 && combo_pitch.step != 0 {
+        combo_pitch.step = 0;
+    }
+}
+
+pub fn attach_spatial_listener(
+    mut commands: Commands,
+    camera_query: Query<Entity, Added<MainCamera>>,
+) {
+    for entity in &camera_query {
+        commands.entity(entity).insert(SpatialListener {
+            left_ear_offset: EAR_OFFSET,
+            right_ear_offset: -EAR_OFFSET,
+        });
+    }
+}
+
+// Positioned sounds play spatially unless mono output is on. Positions are
+// scaled so everything on the playfield stays within rodio's unit distance,
+// where it doesn't fade anything out with distance and only pans
+pub fn apply_panning_to_new_sounds(
+    stereo_settings: Res<StereoSettings>,
+    playfield: Res<Playfield>,
+    mut sound_query: Query<(&mut AudioBus, &mut PlaybackSettings, Has<Transform>), Added<AudioBus>>,
+) {
+    if stereo_settings.mono {
+        return;
+    }
+    let size = playfield.size;
+    let furthest = Vec2::new(size.x / 2.0 + EAR_OFFSET.x, size.y / 2.0).length();
+    for (mut bus, mut playback_settings, positioned) in &mut sound_query {
+        if !positioned {
+            continue;
+        }
+        playback_settings.spatial = true;
+        playback_settings.spatial_scale = Some(SpatialScale::new_2d(1.0 / furthest));
+        bus.volume *= PANNED_LOUDNESS;
     }
 }
@@ -0,0 +1,113 @@
+use bevy::{prelude::*, time::TimeUpdateStrategy};
+use bevy_rand::prelude::{GlobalEntropy, WyRand};
+use rand_core::RngCore;
+
+use crate::{
+    components::bubbles::*,
+    game_states::GameState,
+    resources::{benchmark::Benchmark, bubbles::{BubbleChances, BubbleSpawnTimer}, cache::MeshCache, camera::Playfield, debug::PerfStats},
+    util,
+};
+
+// Special bubbles popped together every trigger, so their effects overlap
+const TRIGGERED_TYPES: [BubbleType; 4] = [
+    BubbleType::Mega,
+    BubbleType::ScatterShot,
+    BubbleType::Beam,
+    BubbleType::BlackHole,
+];
+
+pub fn benchmark_running(
+    benchmark: Option<Res<Benchmark>>,
+) -> bool {
+    benchmark.is_some()
+}
+
+pub fn setup_benchmark_scenario(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mesh_cache: Res<MeshCache>,
+    benchmark: Res<Benchmark>,
+    mut spawn_timer: ResMut<BubbleSpawnTimer>,
+    mut chances: ResMut<BubbleChances>,
+    mut random: ResMut<GlobalEntropy<WyRand>>,
+    playfield: Res<Playfield>,
+) {
+    commands.insert_resource(TimeUpdateStrategy::ManualDuration(benchmark.tick_duration()));
+
+    spawn_timer.action_timer.amount = benchmark.spawn_rate;
+    chances.set_chance(BubbleType::Normal, 100.0);
+    chances.set_chance(BubbleType::Mega, 2.0);
+    chances.set_chance(BubbleType::ScatterShot, 5.0);
+    chances.set_chance(BubbleType::Beam, 5.0);
+    chances.set_chance(BubbleType::BlackHole, 2.0);
+
+    let bounds = playfield.bounds();
+    for _ in 0..benchmark.initial_bubbles {
+        let position = Vec2::new(
+            util::random_f32(random.next_u64(), bounds.min.x, bounds.max.x),
+            util::random_f32(random.next_u64(), bounds.min.y, bounds.max.y),
+        );
+        let velocity = Vec2::new(0.0, util::random_f32(random.next_u64(), 50.0, 150.0));
+        commands.spawn(BubbleBundle::from_type(
+            &mesh_cache,
+            &mut materials,
+            BubbleType::Normal,
+            position,
+            velocity,
+        ));
+    }
+}
+
+// Every so often one of each special bubble gets spawned already popped, which
+// sets off a cascade through the crowd
+pub fn drive_benchmark_scenario(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mesh_cache: Res<MeshCache>,
+    benchmark: Res<Benchmark>,
+    mut random: ResMut<GlobalEntropy<WyRand>>,
+    playfield: Res<Playfield>,
+) {
+    if benchmark.elapsed_ticks % benchmark.trigger_interval != 0 {
+        return;
+    }
+    let bounds = playfield.bounds();
+    for bubble_type in TRIGGERED_TYPES {
+        let position = Vec2::new(
+            util::random_f32(random.next_u64(), bounds.min.x, bounds.max.x),
+            util::random_f32(random.next_u64(), bounds.min.y, bounds.max.y),
+        );
+        let mut bubble = BubbleBundle::from_type(&mesh_cache, &mut materials, bubble_type, position, Vec2::ZERO);
+        bubble.bubble.collapse();
+        commands.spawn(bubble);
+    }
+}
+
+pub fn begin_benchmark_tick(
+    mut benchmark: ResMut<Benchmark>,
+) {
+    benchmark.start_tick();
+}
+
+pub fn end_benchmark_tick(
+    mut commands: Commands,
+    mut benchmark: ResMut<Benchmark>,
+    perf_stats: Res<PerfStats>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    mut app_exit: EventWriter<AppExit>,
+) {
+    benchmark.end_tick(&perf_stats);
+    if !benchmark.is_finished() {
+        return;
+    }
+
+    println!("{}", benchmark.report());
+    commands.remove_resource::<Benchmark>();
+    commands.insert_resource(TimeUpdateStrategy::Automatic);
+    if benchmark.exit_when_done {
+        app_exit.send(AppExit::Success);
+    } else {
+        next_game_state.set(GameState::MainMenu);
+    }
+}
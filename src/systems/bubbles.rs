@@ -5,10 +5,26 @@ pub mod combat;
 pub mod shockwave;
 pub mod movement;
 
-#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum BubbleSystemSet {
     Spawning,
     Combat,
     Shockwave,
     Movement,
 }
+
+impl BubbleSystemSet {
+    // in the order they run
+    pub fn all() -> [BubbleSystemSet; 4] {
+        [BubbleSystemSet::Spawning, BubbleSystemSet::Movement, BubbleSystemSet::Combat, BubbleSystemSet::Shockwave]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            BubbleSystemSet::Spawning => "Spawning",
+            BubbleSystemSet::Combat => "Combat",
+            BubbleSystemSet::Shockwave => "Shockwave",
+            BubbleSystemSet::Movement => "Movement",
+        }
+    }
+}
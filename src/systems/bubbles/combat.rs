@@ -42,6 +42,7 @@ pub fn advance_bubble_collapse(
                         radius: collider.radius,
                         color: materials.get(material).map(|mat| mat.color).unwrap_or(Color::WHITE),
                         bubble_type: bubble.bubble_type,
+                        triggered_by_user: bubble.popped_by_user,
                     });
                 }
             }
@@ -59,10 +60,19 @@ pub fn bubble_clicked(
             if bubble.state == BubbleState::Popped {
                 continue;
             }
-            if collider.is_point_inside(transform.translation.truncate(), event.position) {
+            if util::circle_close_to_line(
+                transform.translation.truncate(),
+                collider.radius,
+                event.path_start,
+                event.position,
+                event.radius * 2.0,
+                true,
+            ) {
                 bubble.collapse();
+                bubble.popped_by_user = true;
                 collapse_event.send(BubbleCollapsedEvent {
                     triggered_by_user: true,
+                    source: None,
                     score_change: 0,
                 });
             }
@@ -73,10 +83,10 @@ pub fn bubble_clicked(
 pub fn bubble_hit_by_shockwave(
     time: Res<Time>,
     mut collapse_event: EventWriter<BubbleCollapsedEvent>,
-    mut shockwave_query: Query<(&Transform, &Collider), With<BubbleShockwave>>,
+    mut shockwave_query: Query<(&Transform, &Collider, &BubbleShockwave)>,
     mut bubble_query: Query<(&Transform, &Collider, &Velocity, &mut Bubble)>,
 ) {
-    for (shockwave_transform, shockwave_collider) in &mut shockwave_query {
+    for (shockwave_transform, shockwave_collider, shockwave) in &mut shockwave_query {
         for (
             bubble_transform,
             bubble_collider,
@@ -98,6 +108,7 @@ pub fn bubble_hit_by_shockwave(
                 bubble.collapse();
                 collapse_event.send(BubbleCollapsedEvent {
                     triggered_by_user: false,
+                    source: Some(shockwave.source),
                     score_change: 1,
                 });
             }
@@ -135,6 +146,7 @@ pub fn bubble_in_black_hole(
                     bubble.collapse();
                     collapse_event.send(BubbleCollapsedEvent {
                         triggered_by_user: false,
+                        source: Some(BubbleType::BlackHole),
                         score_change: 1,
                     });
                 }
@@ -164,6 +176,7 @@ pub fn bubble_hit_by_beam(
                 bubble.collapse();
                 collapse_event.send(BubbleCollapsedEvent {
                     triggered_by_user: false,
+                    source: Some(BubbleType::Beam),
                     score_change: 1,
                 });
             }
@@ -8,11 +8,16 @@ use rand_core::RngCore;
 
 use crate::components::bubbles::*;
 use crate::components::physics::Collider;
-use crate::resources::audio::AudioLimiter;
+use crate::resources::audio::{AudioCategory, AudioLimiter, ComboPitch, PitchSettings};
 use crate::resources::bubbles::*;
 use crate::resources::cache::{MeshCache, AudioCache};
 use crate::util::{self, ActionTimer};
 
+// Black holes far from the middle of the playfield are a little quieter, down
+// to this much of their volume at this distance and beyond
+const BLACK_HOLE_FAR_VOLUME: f32 = 0.7;
+const BLACK_HOLE_FAR_DISTANCE: f32 = 700.0;
+
 pub fn spawn_shockwaves(
     mut commands: Commands,
     mesh_cache: Res<MeshCache>,
@@ -20,6 +25,8 @@ pub fn spawn_shockwaves(
     mut audio_limiter: ResMut<AudioLimiter>,
     mut materials: ResMut<Assets<ColorMaterial>>,
     mut random: ResMut<GlobalEntropy<WyRand>>,
+    pitch_settings: Res<PitchSettings>,
+    mut combo_pitch: ResMut<ComboPitch>,
     mut bubble_destroyed_event: EventReader<BubbleDestroyedEvent>,
 ) {
     for event in bubble_destroyed_event.read() {
@@ -31,11 +38,12 @@ pub fn spawn_shockwaves(
                     &audio_cache,
                     &mut audio_limiter,
                     &mut materials,
-                    &mut random,
+                    pitch_settings.speed_for_step(combo_pitch.step),
                     event.position,
                     event.radius,
                     event.color,
                 );
+                combo_pitch.step += 1;
             }
             BubbleType::Mega => {
                 spawn_mega_shockwave(
@@ -95,7 +103,7 @@ fn spawn_normal_shockwave(
     audio_cache: &AudioCache,
     audio_limiter: &mut AudioLimiter,
     materials: &mut Assets<ColorMaterial>,
-    random: &mut GlobalEntropy<WyRand>,
+    speed: f32,
     position: Vec2,
     radius: f32,
     color: Color,
@@ -108,7 +116,7 @@ fn spawn_normal_shockwave(
         transform: Transform::from_translation(position.extend(-(position.x / 1000.0 + position.y)))
             .with_scale(Vec3::splat(radius)),
         timed_effect: TimedEffect::new(Duration::from_secs_f32(0.2)),
-        bubble_shockwave: BubbleShockwave::new(radius, 50.0),
+        bubble_shockwave: BubbleShockwave::new(radius, 50.0, BubbleType::Normal),
         collider: Collider {
             radius,
             ..Default::default()
@@ -118,8 +126,10 @@ fn spawn_normal_shockwave(
     audio_limiter.play_if_allowed(
         &mut commands,
         audio_cache.bubble_pop.clone(),
+        AudioCategory::Sfx,
+        Some(position),
         PlaybackSettings {
-            speed: util::random_f32(random.next_u64(), 0.7, 1.3),
+            speed,
             mode: PlaybackMode::Despawn,
             ..default()
         },
@@ -145,7 +155,7 @@ fn spawn_mega_shockwave(
         transform: Transform::from_translation(position.extend(-(position.x / 1000.0 + position.y)))
             .with_scale(Vec3::splat(radius)),
         timed_effect: TimedEffect::new(Duration::from_secs_f32(1.0)),
-        bubble_shockwave: BubbleShockwave::new(radius, 500.0),
+        bubble_shockwave: BubbleShockwave::new(radius, 500.0, BubbleType::Mega),
         collider: Collider {
             radius,
             ..Default::default()
@@ -155,6 +165,8 @@ fn spawn_mega_shockwave(
     audio_limiter.play_if_allowed(
         &mut commands,
         audio_cache.bubble_explosion.clone(),
+        AudioCategory::Sfx,
+        Some(position),
         PlaybackSettings {
             speed: util::random_f32(random.next_u64(), 0.9, 1.1),
             mode: PlaybackMode::Despawn,
@@ -193,9 +205,12 @@ fn spawn_black_hole(
     audio_limiter.play_if_allowed(
         &mut commands,
         audio_cache.bubble_black_hole.clone(),
+        AudioCategory::Sfx,
+        Some(position),
         PlaybackSettings {
             speed: util::random_f32(random.next_u64(), 0.9, 1.1),
             mode: PlaybackMode::Despawn,
+            volume: Volume::new(1.0 - (1.0 - BLACK_HOLE_FAR_VOLUME) * (position.length() / BLACK_HOLE_FAR_DISTANCE).min(1.0)),
             ..default()
         },
     );
@@ -217,7 +232,7 @@ fn spawn_scatter_shot(
             radius,
             variation,
             Duration::from_secs_f32(0.2),
-            BubbleShockwave::new(0.0, 50.0),
+            BubbleShockwave::new(0.0, 50.0, BubbleType::ScatterShot),
             color,
         ),
         transform: Transform::from_translation(position.extend(0.0)),
@@ -250,6 +265,8 @@ fn spawn_beam(
     audio_limiter.play_if_allowed(
         &mut commands,
         audio_cache.bubble_beam.clone(),
+        AudioCategory::Sfx,
+        Some(position),
         PlaybackSettings {
             speed: util::random_f32(random.next_u64(), 0.9, 1.1),
             mode: PlaybackMode::Despawn,
@@ -1,7 +1,6 @@
 use std::time::Duration;
 
 use bevy::prelude::*;
-use bevy::window::PrimaryWindow;
 use bevy_rand::prelude::GlobalEntropy;
 use bevy_rand::prelude::WyRand;
 use rand_core::RngCore;
@@ -9,10 +8,13 @@ use rand_core::RngCore;
 use crate::components::bubbles::*;
 use crate::resources::bubbles::*;
 use crate::resources::cache::MeshCache;
+use crate::resources::camera::Playfield;
 use crate::util;
-use crate::util::get_viewport_bounds;
 use crate::util::ActionTimer;
 
+// How far past the bottom and top of the playfield bubbles spawn and despawn
+pub const SPAWN_MARGIN: f32 = 50.0;
+
 pub fn init_bubble_spawner(
     mut commands: Commands,
     mut bubble_chances: ResMut<BubbleChances>,
@@ -40,14 +42,9 @@ pub fn spawn_bubbles(
     time: Res<Time>,
     mut random: ResMut<GlobalEntropy<WyRand>>,
     chances: Res<BubbleChances>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    camera_query: Query<(&Camera, &GlobalTransform)>,
+    playfield: Res<Playfield>,
 ) {
-    let screen_bounds = get_viewport_bounds(&window_query, &camera_query)
-        .unwrap_or(Rect {
-            min: Vec2::new(-400.0, -400.0),
-            max: Vec2::new(500.0, 500.0),
-        });
+    let screen_bounds = playfield.bounds();
 
     let to_spawn = spawn_timer.action_timer.tick(time.delta()).unwrap_or(0);
 
@@ -60,7 +57,7 @@ pub fn spawn_bubbles(
             &mesh_cache,
             &mut materials,
             bubble_type,
-            Vec2::new(x_pos, screen_bounds.min.y - 50.0),
+            Vec2::new(x_pos, screen_bounds.min.y - SPAWN_MARGIN),
             Vec2::new(0.0, y_vel),
         ));
     }
@@ -68,15 +65,12 @@ pub fn spawn_bubbles(
 
 pub fn despawn_bubbles(
     mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    camera_query: Query<(&Camera, &GlobalTransform)>,
+    playfield: Res<Playfield>,
     bubble_query: Query<(Entity, &Transform), With<Bubble>>,
 ) {
-    let boundary = get_viewport_bounds(&window_query, &camera_query)
-        .map(|bounds| bounds.max.y)
-        .unwrap_or(0.0);
+    let boundary = playfield.bounds().max.y;
     for (entity, transform) in bubble_query.iter() {
-        if transform.translation.y > boundary + 50.0 {
+        if transform.translation.y > boundary + SPAWN_MARGIN {
             commands.entity(entity).despawn_recursive();
         }
     }
@@ -1,9 +1,85 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, render::camera::{ScalingMode, Viewport}, window::{PrimaryWindow, WindowResized}};
+
+use crate::{components::{camera::MainCamera, ui::{MainMenuRoot, PauseMenuRoot, UpgradesMenuRoot}}, resources::{camera::{Playfield, PlayfieldScaling}, embedding::{BubbleCollapseConfig, CameraOwnership}}};
 
 pub fn spawn_camera(
     mut commands: Commands,
 ) {
     let camera_pos = Vec3::new(0.0, 0.0, 10.0);
     let camera_transform = Transform::from_translation(camera_pos);
-    commands.spawn((Camera2d::default(), camera_transform));
+    commands.spawn((Camera2d::default(), camera_transform, MainCamera));
+}
+
+pub fn update_camera_scaling(
+    playfield: Res<Playfield>,
+    config: Option<Res<BubbleCollapseConfig>>,
+    mut resize_events: EventReader<WindowResized>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    added_camera_query: Query<(), Added<MainCamera>>,
+    mut camera_query: Query<(&mut Camera, &mut OrthographicProjection), With<MainCamera>>,
+) {
+    let resized = resize_events.read().count() > 0;
+    let camera_added = !added_camera_query.is_empty();
+    if !resized && !camera_added && !playfield.is_changed() {
+        return;
+    }
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+
+    for (mut camera, mut projection) in &mut camera_query {
+        projection.scaling_mode = match playfield.scaling {
+            PlayfieldScaling::Letterbox | PlayfieldScaling::Expand => ScalingMode::AutoMin {
+                min_width: playfield.size.x,
+                min_height: playfield.size.y,
+            },
+            PlayfieldScaling::Stretch => ScalingMode::Fixed {
+                width: playfield.size.x,
+                height: playfield.size.y,
+            },
+        };
+
+        // a host's camera may only cover part of the window, so its viewport is its own
+        if config.as_ref().is_some_and(|config| config.camera == CameraOwnership::Host) {
+            continue;
+        }
+        camera.viewport = match playfield.scaling {
+            PlayfieldScaling::Letterbox => letterbox_viewport(window.physical_size(), playfield.aspect_ratio()),
+            PlayfieldScaling::Expand | PlayfieldScaling::Stretch => None,
+        };
+    }
+}
+
+fn letterbox_viewport(window_size: UVec2, aspect_ratio: f32) -> Option<Viewport> {
+    if window_size.x == 0 || window_size.y == 0 {
+        return None;
+    }
+    let window_aspect_ratio = window_size.x as f32 / window_size.y as f32;
+    let size = if window_aspect_ratio > aspect_ratio {
+        UVec2::new((window_size.y as f32 * aspect_ratio) as u32, window_size.y)
+    } else {
+        UVec2::new(window_size.x, (window_size.x as f32 / aspect_ratio) as u32)
+    };
+    Some(Viewport {
+        physical_position: (window_size - size) / 2,
+        physical_size: size.max(UVec2::ONE),
+        ..default()
+    })
+}
+
+type AddedMenu = Or<(Added<MainMenuRoot>, Added<PauseMenuRoot>, Added<UpgradesMenuRoot>)>;
+
+// Menus would otherwise end up on whichever camera bevy picks, which in a host
+// app with cameras of its own isn't necessarily ours
+pub fn target_menus_at_main_camera(
+    mut commands: Commands,
+    camera_query: Query<Entity, With<MainCamera>>,
+    menu_query: Query<Entity, AddedMenu>,
+) {
+    let Ok(camera) = camera_query.get_single() else {
+        return;
+    };
+    for entity in &menu_query {
+        commands.entity(entity).insert(TargetCamera(camera));
+    }
 }
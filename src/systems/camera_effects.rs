@@ -0,0 +1,89 @@
+use bevy::prelude::*;
+
+use crate::components::camera::{CameraEffectsAnchor, MainCamera};
+use crate::resources::bubbles::BubbleDestroyedEvent;
+use crate::resources::camera_effects::*;
+
+pub fn attach_camera_effects_anchor(
+    mut commands: Commands,
+    camera_query: Query<
+        (Entity, &Transform, &OrthographicProjection),
+        (With<MainCamera>, Without<CameraEffectsAnchor>)
+    >,
+) {
+    for (entity, transform, projection) in &camera_query {
+        commands.entity(entity).insert(CameraEffectsAnchor {
+            translation: transform.translation,
+            scale: projection.scale,
+        });
+    }
+}
+
+pub fn add_trauma_from_explosions(
+    settings: Res<CameraEffectsSettings>,
+    mut effects: ResMut<CameraEffects>,
+    mut bubble_destroyed_event: EventReader<BubbleDestroyedEvent>,
+) {
+    let mut trauma = 0.0;
+    let mut destroyed = 0;
+    for event in bubble_destroyed_event.read() {
+        trauma += settings.trauma_for(event.bubble_type);
+        destroyed += 1;
+    }
+    if destroyed == 0 || !settings.enabled {
+        return;
+    }
+
+    // a frame where hundreds of bubbles go at once should hit harder than a
+    // lone mega, but not so much that it saturates instantly
+    let frame_scale = 1.0 + (destroyed as f32).ln() * 0.25;
+    effects.add_trauma(trauma * frame_scale);
+
+    if destroyed >= settings.zoom_punch_threshold {
+        effects.add_zoom_punch(destroyed as f32 / (settings.zoom_punch_threshold as f32 * 4.0));
+    }
+}
+
+pub fn apply_camera_effects(
+    time: Res<Time>,
+    settings: Res<CameraEffectsSettings>,
+    mut effects: ResMut<CameraEffects>,
+    mut camera_query: Query<(&mut Transform, &mut OrthographicProjection, &CameraEffectsAnchor), With<MainCamera>>,
+) {
+    let delta = time.delta_secs();
+    effects.elapsed += delta;
+    effects.trauma = (effects.trauma - settings.trauma_decay * delta).max(0.0);
+    effects.zoom_punch *= 1.0 - (settings.zoom_punch_decay * delta).min(1.0);
+    if !settings.enabled {
+        effects.clear();
+    }
+
+    let shake = effects.shake();
+    let t = effects.elapsed * settings.shake_frequency;
+    let offset = Vec2::new(shake_noise(t, 0.0), shake_noise(t, 17.3)) * settings.max_offset * shake;
+    let rotation = shake_noise(t, 42.1) * settings.max_rotation * shake;
+    let zoom = 1.0 - effects.zoom_punch * settings.max_zoom_punch;
+
+    for (mut transform, mut projection, anchor) in &mut camera_query {
+        transform.translation = anchor.translation + offset.extend(0.0);
+        transform.rotation = Quat::from_rotation_z(rotation);
+        projection.scale = anchor.scale * zoom;
+    }
+}
+
+pub fn reset_camera_effects(
+    mut effects: ResMut<CameraEffects>,
+    mut camera_query: Query<(&mut Transform, &mut OrthographicProjection, &CameraEffectsAnchor), With<MainCamera>>,
+) {
+    effects.clear();
+    for (mut transform, mut projection, anchor) in &mut camera_query {
+        transform.translation = anchor.translation;
+        transform.rotation = Quat::IDENTITY;
+        projection.scale = anchor.scale;
+    }
+}
+
+// Cheap smooth noise in [-1, 1] made from a couple of out-of-phase sines
+fn shake_noise(t: f32, seed: f32) -> f32 {
+    ((t + seed).sin() + (t * 1.73 + seed * 2.31).sin() * 0.6 + (t * 3.17 + seed * 0.77).sin() * 0.3) / 1.9
+}
@@ -0,0 +1,218 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::io::{BufWriter, Write};
+
+use bevy::{color::palettes::css, ecs::system::SystemParam, prelude::*, window::PrimaryWindow};
+
+use crate::{
+    components::{bubbles::*, camera::MainCamera, particles::Particle, physics::{Collider, Velocity}, popups::ScorePopup},
+    resources::{camera::Playfield, debug::{DebugTools, PerfStats}},
+    systems::bubbles::{spawning::SPAWN_MARGIN, BubbleSystemSet},
+    util,
+};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::persistence;
+
+// goes next to the saves. Browsers have nowhere to write it
+#[cfg(not(target_arch = "wasm32"))]
+const PERF_CSV_NAME: &str = "perf_metrics.csv";
+
+// How many entities there are of each kind worth watching
+#[derive(SystemParam)]
+pub struct EntityCounts<'w, 's> {
+    entities: Query<'w, 's, ()>,
+    bubbles: Query<'w, 's, (), With<Bubble>>,
+    shockwaves: Query<'w, 's, (), With<BubbleShockwave>>,
+    beams: Query<'w, 's, (), With<BubbleBeam>>,
+    black_holes: Query<'w, 's, (), With<BubbleBlackHole>>,
+    scatter_shots: Query<'w, 's, (), With<BubbleScatterShotSpawner>>,
+    particles: Query<'w, 's, (), With<Particle>>,
+    popups: Query<'w, 's, (), With<ScorePopup>>,
+    audio_players: Query<'w, 's, (), With<AudioPlayer>>,
+}
+
+impl EntityCounts<'_, '_> {
+    pub fn rows(&self) -> [(&'static str, usize); 9] {
+        [
+            ("Entities", self.entities.iter().count()),
+            ("Bubbles", self.bubbles.iter().count()),
+            ("Shockwaves", self.shockwaves.iter().count()),
+            ("Beams", self.beams.iter().count()),
+            ("Black Holes", self.black_holes.iter().count()),
+            ("Scatter Shots", self.scatter_shots.iter().count()),
+            ("Particles", self.particles.iter().count()),
+            ("Popups", self.popups.iter().count()),
+            ("Audio Players", self.audio_players.iter().count()),
+        ]
+    }
+}
+
+pub fn gizmos_enabled(
+    debug_tools: Res<DebugTools>,
+) -> bool {
+    debug_tools.show_gizmos
+}
+
+pub fn record_frame_time(
+    time: Res<Time<Real>>,
+    mut perf_stats: ResMut<PerfStats>,
+) {
+    perf_stats.push_frame_time(time.delta_secs());
+}
+
+pub fn start_set_timer(set: BubbleSystemSet) -> impl FnMut(ResMut<PerfStats>) {
+    move |mut perf_stats: ResMut<PerfStats>| perf_stats.start_set(set)
+}
+
+pub fn end_set_timer(set: BubbleSystemSet) -> impl FnMut(ResMut<PerfStats>) {
+    move |mut perf_stats: ResMut<PerfStats>| perf_stats.end_set(set)
+}
+
+// Dropping the writer flushes whatever hasn't been written out yet
+#[cfg(not(target_arch = "wasm32"))]
+pub fn close_perf_csv(
+    mut perf_stats: ResMut<PerfStats>,
+) {
+    perf_stats.csv = None;
+}
+
+// One row per frame while recording, with a header whenever a new file is
+// started. Turning recording off closes the file
+#[cfg(not(target_arch = "wasm32"))]
+pub fn write_perf_csv(
+    mut debug_tools: ResMut<DebugTools>,
+    mut perf_stats: ResMut<PerfStats>,
+    entity_counts: EntityCounts,
+) {
+    if !debug_tools.record_csv {
+        perf_stats.csv = None;
+        return;
+    }
+
+    let counts = entity_counts.rows();
+    if perf_stats.csv.is_none() {
+        let file = match persistence::create_file(PERF_CSV_NAME) {
+            Ok(file) => file,
+            Err(error) => {
+                warn!("Failed to create {}: {}", PERF_CSV_NAME, error);
+                debug_tools.record_csv = false;
+                return;
+            }
+        };
+        let mut header = vec!["frame".to_string(), "frame_time_ms".to_string()];
+        header.extend(counts.iter().map(|(name, _)| name.to_lowercase().replace(' ', "_")));
+        header.extend(BubbleSystemSet::all().iter().map(|set| format!("{}_ms", set.name().to_lowercase())));
+        let mut writer = BufWriter::new(file);
+        if let Err(error) = writeln!(writer, "{}", header.join(",")) {
+            warn!("Failed to write {}: {}", PERF_CSV_NAME, error);
+            debug_tools.record_csv = false;
+            return;
+        }
+        perf_stats.csv = Some(writer);
+    }
+
+    let mut row = vec![perf_stats.frame.to_string(), format!("{:.3}", perf_stats.last_frame_time() * 1000.0)];
+    row.extend(counts.iter().map(|(_, count)| count.to_string()));
+    row.extend(BubbleSystemSet::all().iter().map(|set| format!("{:.3}", perf_stats.set_time(*set).as_secs_f64() * 1000.0)));
+    if let Some(writer) = perf_stats.csv.as_mut() {
+        if let Err(error) = writeln!(writer, "{}", row.join(",")) {
+            warn!("Failed to write {}: {}", PERF_CSV_NAME, error);
+            debug_tools.record_csv = false;
+        }
+    }
+}
+
+pub fn draw_collider_gizmos(
+    mut gizmos: Gizmos,
+    collider_query: Query<(&GlobalTransform, &Collider)>,
+) {
+    for (transform, collider) in &collider_query {
+        gizmos.circle_2d(transform.translation().truncate(), collider.radius, css::LIME);
+    }
+}
+
+// `continuous_circle_collision` checks whether the other circle comes within
+// both radii of the bubble's path this frame, which is the same as the bubble
+// sweeping out this capsule
+pub fn draw_swept_collision_gizmos(
+    mut gizmos: Gizmos,
+    time: Res<Time>,
+    bubble_query: Query<(&Transform, &Collider, &Velocity), With<Bubble>>,
+) {
+    for (transform, collider, velocity) in &bubble_query {
+        let start = transform.translation.truncate();
+        let end = start + velocity.velocity * time.delta_secs();
+        draw_capsule(&mut gizmos, start, end, collider.radius, css::ORANGE);
+    }
+}
+
+// Beams hit anything touching an endless vertical line, `width` wide
+pub fn draw_beam_gizmos(
+    mut gizmos: Gizmos,
+    playfield: Res<Playfield>,
+    beam_query: Query<(&Transform, &BubbleBeam)>,
+) {
+    let bounds = playfield.bounds();
+    for (transform, beam) in &beam_query {
+        let x = transform.translation.x;
+        gizmos.line_2d(Vec2::new(x, bounds.min.y), Vec2::new(x, bounds.max.y), css::YELLOW);
+        for side in [-1.0, 1.0] {
+            let edge = x + side * beam.width / 2.0;
+            gizmos.line_2d(Vec2::new(edge, bounds.min.y), Vec2::new(edge, bounds.max.y), css::GOLD);
+        }
+    }
+}
+
+// The pull each bubble inside a black hole gets this frame, along with the
+// radius where bubbles get swallowed
+pub fn draw_black_hole_gizmos(
+    mut gizmos: Gizmos,
+    black_hole_query: Query<(&Transform, &Collider, &BubbleBlackHole)>,
+    bubble_query: Query<(&Transform, &Collider), With<Bubble>>,
+) {
+    for (black_hole_transform, black_hole_collider, black_hole) in &black_hole_query {
+        let center = black_hole_transform.translation.truncate();
+        gizmos.circle_2d(center, black_hole.max_radius / 5.0, css::RED);
+        for (bubble_transform, bubble_collider) in &bubble_query {
+            let position = bubble_transform.translation.truncate();
+            let direction = center - position;
+            let distance = direction.length();
+            if distance == 0.0 || distance > black_hole_collider.radius + bubble_collider.radius {
+                continue;
+            }
+            let force = (black_hole.strength / distance).min(black_hole.max_pull);
+            gizmos.arrow_2d(position, position + direction.normalize() * force, css::MAGENTA);
+        }
+    }
+}
+
+// The playfield, the lines bubbles spawn below and despawn above, and what the
+// camera actually shows
+pub fn draw_bounds_gizmos(
+    mut gizmos: Gizmos,
+    playfield: Res<Playfield>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+) {
+    let bounds = playfield.bounds();
+    gizmos.rect_2d(Isometry2d::from_translation(bounds.center()), bounds.size(), css::AQUA);
+
+    let spawn_y = bounds.min.y - SPAWN_MARGIN;
+    let despawn_y = bounds.max.y + SPAWN_MARGIN;
+    gizmos.line_2d(Vec2::new(bounds.min.x, spawn_y), Vec2::new(bounds.max.x, spawn_y), css::SPRING_GREEN);
+    gizmos.line_2d(Vec2::new(bounds.min.x, despawn_y), Vec2::new(bounds.max.x, despawn_y), css::TOMATO);
+
+    if let Some(viewport) = util::get_viewport_bounds(&window_query, &camera_query) {
+        gizmos.rect_2d(Isometry2d::from_translation(viewport.center()), viewport.size(), css::WHITE);
+    }
+}
+
+fn draw_capsule(gizmos: &mut Gizmos, start: Vec2, end: Vec2, radius: f32, color: impl Into<Color> + Copy) {
+    gizmos.circle_2d(start, radius, color);
+    let Some(direction) = (end - start).try_normalize() else {
+        return;
+    };
+    gizmos.circle_2d(end, radius, color);
+    let side = direction.perp() * radius;
+    gizmos.line_2d(start + side, end + side, color);
+    gizmos.line_2d(start - side, end - side, color);
+}
@@ -0,0 +1,32 @@
+use bevy::prelude::*;
+
+use crate::game_states::GameState;
+use crate::resources::embedding::{BubbleCollapseConfig, CameraOwnership, InputSource};
+
+// Without a config the game is running on its own, so it owns everything
+
+pub fn owns_camera(
+    config: Option<Res<BubbleCollapseConfig>>,
+) -> bool {
+    config.is_none_or(|config| config.camera == CameraOwnership::Game)
+}
+
+pub fn reads_window_input(
+    config: Option<Res<BubbleCollapseConfig>>,
+) -> bool {
+    config.is_none_or(|config| config.input == InputSource::Window)
+}
+
+// Goes through `NextState` instead of being the initial state, since the
+// initial state gets entered before any `Startup` system has filled the caches
+pub fn enter_starting_state(
+    config: Option<Res<BubbleCollapseConfig>>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+) {
+    let Some(config) = config else {
+        return;
+    };
+    if config.starting_state != GameState::default() {
+        next_game_state.set(config.starting_state.clone());
+    }
+}
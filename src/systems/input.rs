@@ -0,0 +1,88 @@
+use bevy::prelude::*;
+use bevy_egui::EguiContexts;
+
+use crate::{persistence, resources::input::*};
+
+pub fn load_action_map(
+    mut commands: Commands,
+) {
+    if let Some(mut action_map) = persistence::load::<ActionMap>(ActionMap::SAVE_KEY) {
+        action_map.add_missing_defaults();
+        commands.insert_resource(action_map);
+    }
+}
+
+pub fn save_action_map(
+    action_map: Res<ActionMap>,
+) {
+    if action_map.is_changed() && !action_map.is_added() {
+        persistence::save(ActionMap::SAVE_KEY, action_map.as_ref());
+    }
+}
+
+pub fn update_action_state(
+    action_map: Res<ActionMap>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    gamepads: Query<&Gamepad>,
+    rebind_state: Res<RebindState>,
+    mut action_state: ResMut<ActionState>,
+) {
+    action_state.clear();
+    // whatever gets pressed while rebinding belongs to the new binding, not the game
+    if rebind_state.waiting_for.is_some() {
+        return;
+    }
+
+    for (&action, bindings) in &action_map.bindings {
+        for binding in bindings {
+            let (pressed, just_pressed, just_released) = match *binding {
+                InputBinding::Key(key) => (
+                    keys.pressed(key),
+                    keys.just_pressed(key),
+                    keys.just_released(key),
+                ),
+                InputBinding::Mouse(button) => (
+                    mouse_buttons.pressed(button),
+                    mouse_buttons.just_pressed(button),
+                    mouse_buttons.just_released(button),
+                ),
+                InputBinding::Gamepad(button) => (
+                    gamepads.iter().any(|gamepad| gamepad.pressed(button)),
+                    gamepads.iter().any(|gamepad| gamepad.just_pressed(button)),
+                    gamepads.iter().any(|gamepad| gamepad.just_released(button)),
+                ),
+            };
+            action_state.record(action, pressed, just_pressed, just_released);
+        }
+    }
+}
+
+pub fn capture_rebind(
+    mut contexts: EguiContexts,
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    gamepads: Query<&Gamepad>,
+    mut rebind_state: ResMut<RebindState>,
+    mut action_map: ResMut<ActionMap>,
+) {
+    let Some((action, slot)) = rebind_state.waiting_for else {
+        return;
+    };
+
+    // clicks on the options window itself (like the cancel button) aren't bindings
+    let pointer_over_ui = contexts.ctx_mut().is_pointer_over_area();
+    let binding = keys.get_just_pressed().next().map(|key| InputBinding::Key(*key))
+        .or_else(|| mouse_buttons.get_just_pressed()
+            .next()
+            .filter(|_| !pointer_over_ui)
+            .map(|button| InputBinding::Mouse(*button)))
+        .or_else(|| gamepads.iter()
+            .find_map(|gamepad| gamepad.get_just_pressed().next().copied())
+            .map(InputBinding::Gamepad));
+
+    if let Some(binding) = binding {
+        action_map.rebind(action, slot, binding);
+        rebind_state.waiting_for = None;
+    }
+}
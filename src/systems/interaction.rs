@@ -1,26 +1,277 @@
 use bevy::{prelude::*, window::PrimaryWindow};
+use bevy_egui::EguiContext;
 
-use crate::resources::interaction::*;
+use crate::{
+    components::{camera::MainCamera, interaction::CursorReticle},
+    resources::{cache::MeshCache, camera::Playfield, input::{ActionState, InputAction}, interaction::*},
+    util::{window_to_world_2d, world_to_window_2d},
+};
+
+// Runs after the UI focus system, so `Interaction` already reflects this
+// frame's pointer. Touches are hit-tested against the same nodes separately,
+// since the focus system only follows the first finger
+pub fn update_pointer_capture(
+    mut pointer_capture: ResMut<PointerCapture>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<&Camera, With<MainCamera>>,
+    node_query: Query<(&Interaction, &ComputedNode, &GlobalTransform, &ViewVisibility)>,
+    mut egui_query: Query<&mut EguiContext, With<PrimaryWindow>>,
+) {
+    pointer_capture.hovering_ui = false;
+    pointer_capture.ui_rects.clear();
+    pointer_capture.egui_wants_pointer = egui_query.get_single_mut()
+        .map(|mut context| {
+            let context = context.get_mut();
+            context.wants_pointer_input() || context.is_pointer_over_area()
+        })
+        .unwrap_or(false);
+
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    // ui nodes are laid out in physical pixels inside the camera's viewport
+    let viewport_offset = camera_query.get_single().ok()
+        .and_then(|camera| camera.physical_viewport_rect())
+        .map(|rect| rect.min.as_vec2())
+        .unwrap_or_default();
+    let scale = window.scale_factor();
+
+    for (interaction, node, transform, visibility) in &node_query {
+        if !visibility.get() {
+            continue;
+        }
+        if *interaction != Interaction::None {
+            pointer_capture.hovering_ui = true;
+        }
+        let center = (transform.translation().truncate() + viewport_offset) / scale;
+        pointer_capture.ui_rects.push(Rect::from_center_size(center, node.size() / scale));
+    }
+}
 
 pub fn handle_mouse_click(
     mut mouse_click_event: EventWriter<MouseClickEvent>,
-    mouse_input: Res<ButtonInput<MouseButton>>,
+    actions: Res<ActionState>,
+    pointer_capture: Res<PointerCapture>,
     window_query: Query<&Window, With<PrimaryWindow>>,
-    camera_query: Query<(&Camera, &GlobalTransform)>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
 ) {
     let (Ok(window), Ok((camera, camera_transform))) =
         (window_query.get_single(), camera_query.get_single()) else {
         return;
     };
-    if mouse_input.just_pressed(MouseButton::Left) {
+    if actions.just_pressed(InputAction::Pop) {
         if let Some(window_pos) = window.cursor_position() {
-            let world_pos = camera.viewport_to_world_2d(camera_transform, window_pos);
-            if let Ok(world_pos) = world_pos {
+            if pointer_capture.blocks_mouse(window_pos) {
+                return;
+            }
+            let world_pos = window_to_world_2d(camera, camera_transform, window_pos);
+            if let Some(world_pos) = world_pos {
                 mouse_click_event.send(MouseClickEvent {
                     position: world_pos,
+                    path_start: world_pos,
                     window_position: window_pos,
+                    radius: 0.0,
                 });
             }
         }
     }
 }
+
+pub fn handle_touches(
+    mut mouse_click_event: EventWriter<MouseClickEvent>,
+    touches: Res<Touches>,
+    touch_settings: Res<TouchSettings>,
+    pointer_capture: Res<PointerCapture>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+) {
+    let Ok((camera, camera_transform)) = camera_query.get_single() else {
+        return;
+    };
+    // every finger that lands this frame pops on its own
+    for touch in touches.iter_just_pressed() {
+        let window_pos = touch.position();
+        if pointer_capture.blocks(window_pos) {
+            continue;
+        }
+        if let Some(world_pos) = window_to_world_2d(camera, camera_transform, window_pos) {
+            mouse_click_event.send(MouseClickEvent {
+                position: world_pos,
+                path_start: world_pos,
+                window_position: window_pos,
+                radius: touch_settings.effective_tap_radius(),
+            });
+        }
+    }
+}
+
+pub fn release_virtual_cursor_on_mouse_move(
+    mut virtual_cursor: ResMut<VirtualCursor>,
+    mut menu_focus: ResMut<MenuFocus>,
+    mut cursor_moved_events: EventReader<CursorMoved>,
+) {
+    if cursor_moved_events.read().count() > 0 {
+        virtual_cursor.active = false;
+        menu_focus.active = false;
+    }
+}
+
+pub fn move_virtual_cursor(
+    time: Res<Time>,
+    actions: Res<ActionState>,
+    gamepads: Query<&Gamepad>,
+    playfield: Res<Playfield>,
+    mut virtual_cursor: ResMut<VirtualCursor>,
+) {
+    let mut direction = Vec2::ZERO;
+    for gamepad in &gamepads {
+        let stick = gamepad.left_stick();
+        if stick.length() > virtual_cursor.dead_zone {
+            direction += stick;
+        }
+    }
+    if actions.pressed(InputAction::CursorLeft) {
+        direction.x -= 1.0;
+    }
+    if actions.pressed(InputAction::CursorRight) {
+        direction.x += 1.0;
+    }
+    if actions.pressed(InputAction::CursorDown) {
+        direction.y -= 1.0;
+    }
+    if actions.pressed(InputAction::CursorUp) {
+        direction.y += 1.0;
+    }
+
+    if direction == Vec2::ZERO {
+        virtual_cursor.stop();
+        return;
+    }
+
+    let delta = time.delta_secs();
+    virtual_cursor.active = true;
+    virtual_cursor.accelerate(delta);
+    let bounds = playfield.bounds();
+    let step = direction.clamp_length_max(1.0) * virtual_cursor.speed * delta;
+    virtual_cursor.position = (virtual_cursor.position + step).clamp(bounds.min, bounds.max);
+}
+
+pub fn handle_virtual_cursor_click(
+    mut mouse_click_event: EventWriter<MouseClickEvent>,
+    actions: Res<ActionState>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    mut virtual_cursor: ResMut<VirtualCursor>,
+) {
+    if !actions.just_pressed(InputAction::CursorPop) {
+        return;
+    }
+
+    virtual_cursor.active = true;
+    let window_position = camera_query.get_single().ok()
+        .and_then(|(camera, camera_transform)| world_to_window_2d(camera, camera_transform, virtual_cursor.position))
+        .unwrap_or_default();
+    mouse_click_event.send(MouseClickEvent {
+        position: virtual_cursor.position,
+        path_start: virtual_cursor.position,
+        window_position,
+        radius: 0.0,
+    });
+}
+
+pub fn spawn_cursor_reticle(
+    mut commands: Commands,
+    mesh_cache: Res<MeshCache>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    commands.spawn((
+        Mesh2d(mesh_cache.circle_mesh.clone()),
+        MeshMaterial2d(materials.add(Color::srgba(1.0, 1.0, 1.0, 0.35))),
+        Transform::from_translation(Vec3::new(0.0, 0.0, 600.0))
+            .with_scale(Vec3::splat(12.0)),
+        Visibility::Hidden,
+        CursorReticle,
+    ));
+}
+
+pub fn cleanup_cursor_reticle(
+    mut commands: Commands,
+    query: Query<Entity, With<CursorReticle>>,
+) {
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+pub fn update_cursor_reticle(
+    virtual_cursor: Res<VirtualCursor>,
+    mut reticle_query: Query<(&mut Transform, &mut Visibility), With<CursorReticle>>,
+) {
+    for (mut transform, mut visibility) in &mut reticle_query {
+        transform.translation = virtual_cursor.position.extend(600.0);
+        *visibility = if virtual_cursor.active { Visibility::Visible } else { Visibility::Hidden };
+    }
+}
+
+// Runs after the UI focus system so the focus it fakes here (hovering and
+// pressing buttons) is what the menu button systems see this frame
+pub fn navigate_menu_focus(
+    actions: Res<ActionState>,
+    mut menu_focus: ResMut<MenuFocus>,
+    button_query: Query<(Entity, &GlobalTransform, &ViewVisibility), With<Button>>,
+    mut interaction_query: Query<&mut Interaction, With<Button>>,
+) {
+    // a press only lasts a single frame, just like a mouse click would
+    if let Some(entity) = menu_focus.pressed.take() {
+        if let Ok(mut interaction) = interaction_query.get_mut(entity) {
+            *interaction = Interaction::Hovered;
+        }
+    }
+
+    let up = actions.just_pressed(InputAction::CursorUp);
+    let down = actions.just_pressed(InputAction::CursorDown);
+    let activate = actions.just_pressed(InputAction::MenuConfirm);
+    if up || down || activate {
+        menu_focus.active = true;
+    }
+    if !menu_focus.active {
+        return;
+    }
+
+    // UI space has y pointing down, so this is top to bottom, left to right
+    let mut buttons = button_query.iter()
+        .filter(|(_, _, visibility)| visibility.get())
+        .map(|(entity, transform, _)| (entity, transform.translation().truncate()))
+        .collect::<Vec<(Entity, Vec2)>>();
+    buttons.sort_by(|(_, a), (_, b)| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)));
+    if buttons.is_empty() {
+        menu_focus.focused = None;
+        return;
+    }
+
+    let current = menu_focus.focused
+        .and_then(|focused| buttons.iter().position(|(entity, _)| *entity == focused));
+    let index = match current {
+        Some(index) if up => (index + buttons.len() - 1) % buttons.len(),
+        Some(index) if down => (index + 1) % buttons.len(),
+        Some(index) => index,
+        None => 0,
+    };
+    let focused = buttons[index].0;
+
+    if let Some(previous) = menu_focus.focused.filter(|previous| *previous != focused) {
+        if let Ok(mut interaction) = interaction_query.get_mut(previous) {
+            interaction.set_if_neq(Interaction::None);
+        }
+    }
+    // the first input only picks a button, it doesn't press it straight away
+    let should_press = activate && current.is_some();
+    menu_focus.focused = Some(focused);
+
+    if let Ok(mut interaction) = interaction_query.get_mut(focused) {
+        if should_press {
+            *interaction = Interaction::Pressed;
+            menu_focus.pressed = Some(focused);
+        } else {
+            interaction.set_if_neq(Interaction::Hovered);
+        }
+    }
+}
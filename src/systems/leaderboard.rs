@@ -0,0 +1,81 @@
+use bevy::prelude::*;
+
+use crate::{
+    persistence,
+    resources::{leaderboard::*, replay::{RecordedReplay, Replay}, run::{GameMode, RunSeed}, stats::{GameStats, RunStats}},
+    util,
+};
+
+pub fn load_leaderboard(
+    mut commands: Commands,
+) {
+    if let Some(leaderboard) = persistence::load::<Leaderboard>(Leaderboard::SAVE_KEY) {
+        commands.insert_resource(leaderboard);
+    }
+}
+
+pub fn save_leaderboard(
+    leaderboard: Res<Leaderboard>,
+) {
+    if leaderboard.is_changed() && !leaderboard.is_added() {
+        persistence::save(Leaderboard::SAVE_KEY, leaderboard.as_ref());
+    }
+}
+
+pub fn check_leaderboard(
+    mut commands: Commands,
+    leaderboard: Res<Leaderboard>,
+    game_stats: Res<GameStats>,
+    mode: Res<GameMode>,
+    run_seed: Res<RunSeed>,
+    run_stats: Res<RunStats>,
+    recorded: Option<Res<RecordedReplay>>,
+) {
+    if !leaderboard.qualifies(*mode, game_stats.score) {
+        return;
+    }
+    let name = if leaderboard.last_name.is_empty() {
+        "Player".to_string()
+    } else {
+        leaderboard.last_name.clone()
+    };
+    commands.insert_resource(PendingLeaderboardEntry {
+        entry: LeaderboardEntry {
+            name,
+            mode: *mode,
+            score: game_stats.score,
+            longest_chain: run_stats.longest_chain,
+            date: util::unix_time(),
+            seed: run_seed.current,
+            replay: None,
+        },
+        recorded: recorded.is_some(),
+    });
+}
+
+// The last run's replay gets overwritten by the next one, so entries keep a
+// copy of their own
+pub fn submit_leaderboard_entry(
+    leaderboard: &mut Leaderboard,
+    pending: &PendingLeaderboardEntry,
+) {
+    let mut entry = pending.entry.clone();
+    if pending.recorded {
+        if let Some(replay) = persistence::load::<Replay>(Replay::LAST_RUN_KEY) {
+            let key = format!("replays/{}-{}", entry.date, entry.seed);
+            persistence::save(&key, &replay);
+            entry.replay = Some(key);
+        }
+    }
+    leaderboard.insert(entry);
+}
+
+// Leaving the summary without confirming a name still keeps the entry
+pub fn submit_pending_entry(
+    mut commands: Commands,
+    mut leaderboard: ResMut<Leaderboard>,
+    pending: Res<PendingLeaderboardEntry>,
+) {
+    submit_leaderboard_entry(&mut leaderboard, &pending);
+    commands.remove_resource::<PendingLeaderboardEntry>();
+}
@@ -0,0 +1,108 @@
+use bevy::{audio::Volume, prelude::*};
+
+use crate::{
+    components::{bubbles::Bubble, music::*},
+    game_states::{GameState, PausedState},
+    resources::{audio::{AudioCategory, AudioVolumes}, music::*, stats::Chain},
+};
+
+const INTENSITY_RISE_SPEED: f32 = 2.0;
+const INTENSITY_FALL_SPEED: f32 = 0.4;
+const DUCK_SPEED: f32 = 3.0;
+
+// Every track starts together and loops forever, silent until it's needed, so
+// the play layers always stay in time with the base track. The tracks don't
+// ship with the game, so any that aren't there are left out rather than
+// failing to load
+pub fn spawn_music_tracks(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    let tracks = [
+        (MusicRole::Menu, "music/menu.ogg"),
+        (MusicRole::PlayBase, "music/play_base.ogg"),
+        (MusicRole::PlayIntensity(1), "music/play_layer_1.ogg"),
+        (MusicRole::PlayIntensity(2), "music/play_layer_2.ogg"),
+    ];
+    for (role, path) in tracks {
+        if !track_available(path) {
+            continue;
+        }
+        commands.spawn((
+            AudioPlayer::<AudioSource>(asset_server.load(path)),
+            PlaybackSettings::LOOP.with_volume(Volume::new(0.0)),
+            MusicTrack::new(role),
+        ));
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn track_available(path: &str) -> bool {
+    bevy::asset::io::file::FileAssetReader::get_base_path()
+        .join("assets")
+        .join(path)
+        .exists()
+}
+
+// the browser can only find out by requesting it
+#[cfg(target_arch = "wasm32")]
+fn track_available(_path: &str) -> bool {
+    true
+}
+
+pub fn update_music_mix(
+    time: Res<Time>,
+    settings: Res<MusicSettings>,
+    chain: Res<Chain>,
+    game_state: Res<State<GameState>>,
+    paused_state: Res<State<PausedState>>,
+    bubble_query: Query<(), With<Bubble>>,
+    mut mix: ResMut<MusicMix>,
+) {
+    let delta = time.delta_secs();
+
+    let target_intensity = if *game_state.get() == GameState::InGame {
+        let from_bubbles = bubble_query.iter().count() as f32 / settings.bubbles_per_level;
+        let from_chain = chain.length as f32 / settings.chain_per_level;
+        from_bubbles.max(from_chain)
+    } else {
+        0.0
+    };
+    let speed = if target_intensity > mix.intensity { INTENSITY_RISE_SPEED } else { INTENSITY_FALL_SPEED };
+    mix.intensity = move_towards(mix.intensity, target_intensity, speed * delta);
+
+    let target_duck = if *paused_state.get() == PausedState::Paused { settings.pause_duck } else { 1.0 };
+    mix.duck = move_towards(mix.duck, target_duck, DUCK_SPEED * delta);
+}
+
+pub fn fade_music_tracks(
+    time: Res<Time>,
+    settings: Res<MusicSettings>,
+    volumes: Res<AudioVolumes>,
+    mix: Res<MusicMix>,
+    game_state: Res<State<GameState>>,
+    mut track_query: Query<(&mut MusicTrack, Option<&AudioSink>)>,
+) {
+    let delta = time.delta_secs();
+    let in_game = *game_state.get() == GameState::InGame;
+    let volume = settings.volume * volumes.volume_for(AudioCategory::Music);
+
+    for (mut track, sink) in &mut track_query {
+        let (target, fade_secs) = match track.role {
+            MusicRole::Menu => (if in_game { 0.0 } else { 1.0 }, settings.crossfade_secs),
+            MusicRole::PlayBase => (if in_game { 1.0 } else { 0.0 }, settings.crossfade_secs),
+            MusicRole::PlayIntensity(layer) if in_game => (mix.layer_target(layer), settings.layer_fade_secs),
+            MusicRole::PlayIntensity(_) => (0.0, settings.crossfade_secs),
+        };
+        track.fade = move_towards(track.fade, target, delta / fade_secs);
+
+        // the sink only shows up once the track has loaded
+        if let Some(sink) = sink {
+            sink.set_volume(track.fade * mix.duck * volume);
+        }
+    }
+}
+
+fn move_towards(current: f32, target: f32, max_step: f32) -> f32 {
+    current + (target - current).clamp(-max_step, max_step)
+}
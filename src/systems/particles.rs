@@ -0,0 +1,223 @@
+use std::f32::consts::PI;
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_rand::prelude::WyRand;
+use rand_core::{RngCore, SeedableRng};
+
+use crate::components::bubbles::{BubbleBeam, BubbleBlackHole};
+use crate::components::particles::*;
+use crate::resources::bubbles::BubbleDestroyedEvent;
+use crate::resources::camera::Playfield;
+use crate::resources::particles::*;
+use crate::resources::run::RunSeed;
+use crate::util;
+
+const DROPLET_GRAVITY: f32 = 400.0;
+const PARTICLE_DRAG: f32 = 3.0;
+
+type ParticleQuery<'w, 's> = Query<'w, 's, (&'static mut Particle, &'static mut Sprite, &'static mut Transform, &'static mut Visibility)>;
+
+pub fn init_particle_pool(
+    mut commands: Commands,
+    mut pool: ResMut<ParticlePool>,
+    mut random: ResMut<ParticleRandom>,
+    settings: Res<ParticleSettings>,
+    run_seed: Res<RunSeed>,
+) {
+    random.rng = WyRand::seed_from_u64(run_seed.current);
+    pool.reset();
+    for _ in 0..settings.quality.max_particles() {
+        let entity = commands.spawn((
+            Sprite::from_color(Color::WHITE, Vec2::ONE),
+            Transform::default(),
+            Visibility::Hidden,
+            Particle::default(),
+        )).id();
+        pool.entities.push(entity);
+    }
+}
+
+pub fn cleanup_particles(
+    mut commands: Commands,
+    mut pool: ResMut<ParticlePool>,
+    query: Query<Entity, With<Particle>>,
+) {
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
+    }
+    pool.reset();
+}
+
+pub fn emit_pop_droplets(
+    settings: Res<ParticleSettings>,
+    mut pool: ResMut<ParticlePool>,
+    mut random: ResMut<ParticleRandom>,
+    mut bubble_destroyed_event: EventReader<BubbleDestroyedEvent>,
+    mut particle_query: ParticleQuery,
+) {
+    for event in bubble_destroyed_event.read() {
+        let count = (event.radius * 0.6 * settings.quality.density()).round() as u32;
+        for _ in 0..count {
+            let angle = util::random_f32(random.rng.next_u64(), 0.0, PI * 2.0);
+            let speed = util::random_f32(random.rng.next_u64(), 80.0, 220.0);
+            let direction = Vec2::new(angle.cos(), angle.sin());
+            emit_particle(
+                &mut pool,
+                &mut particle_query,
+                event.position + direction * event.radius * 0.5,
+                event.color,
+                ParticleKind::Droplet,
+                direction * speed,
+                util::random_f32(random.rng.next_u64(), 2.0, 4.0),
+                Duration::from_secs_f32(util::random_f32(random.rng.next_u64(), 0.4, 0.7)),
+            );
+        }
+    }
+}
+
+pub fn emit_beam_sparks(
+    time: Res<Time>,
+    settings: Res<ParticleSettings>,
+    materials: Res<Assets<ColorMaterial>>,
+    mut pool: ResMut<ParticlePool>,
+    mut random: ResMut<ParticleRandom>,
+    playfield: Res<Playfield>,
+    beam_query: Query<(&Transform, &BubbleBeam, &MeshMaterial2d<ColorMaterial>), Without<Particle>>,
+    mut particle_query: ParticleQuery,
+) {
+    let bounds = playfield.bounds();
+    for (beam_transform, beam, material) in &beam_query {
+        if beam.width < 1.0 {
+            continue;
+        }
+        let position = beam_transform.translation.truncate();
+        let (min_y, max_y) = (bounds.min.y, bounds.max.y);
+        let color = materials.get(material).map(|mat| mat.color.with_alpha(1.0)).unwrap_or(Color::WHITE);
+
+        let count = emission_count(&mut random.rng, 120.0 * settings.quality.density() * time.delta_secs());
+        for _ in 0..count {
+            let side = if util::random_f32(random.rng.next_u64(), 0.0, 1.0) < 0.5 { -1.0 } else { 1.0 };
+            let y = util::random_f32(random.rng.next_u64(), min_y, max_y);
+            let speed = util::random_f32(random.rng.next_u64(), 60.0, 180.0);
+            let drift = util::random_f32(random.rng.next_u64(), -40.0, 40.0);
+            emit_particle(
+                &mut pool,
+                &mut particle_query,
+                Vec2::new(position.x + side * beam.width / 2.0, y),
+                color,
+                ParticleKind::Spark,
+                Vec2::new(side * speed, drift),
+                util::random_f32(random.rng.next_u64(), 1.5, 3.0),
+                Duration::from_secs_f32(util::random_f32(random.rng.next_u64(), 0.2, 0.4)),
+            );
+        }
+    }
+}
+
+pub fn emit_black_hole_debris(
+    time: Res<Time>,
+    settings: Res<ParticleSettings>,
+    mut pool: ResMut<ParticlePool>,
+    mut random: ResMut<ParticleRandom>,
+    black_hole_query: Query<(&Transform, &BubbleBlackHole), Without<Particle>>,
+    mut particle_query: ParticleQuery,
+) {
+    for (black_hole_transform, black_hole) in &black_hole_query {
+        let center = black_hole_transform.translation.truncate();
+        let count = emission_count(&mut random.rng, 60.0 * settings.quality.density() * time.delta_secs());
+        for _ in 0..count {
+            let angle = util::random_f32(random.rng.next_u64(), 0.0, PI * 2.0);
+            let distance = util::random_f32(random.rng.next_u64(), 0.5, 1.0) * black_hole.max_radius;
+            let shade = util::random_f32(random.rng.next_u64(), 0.1, 0.4);
+            emit_particle(
+                &mut pool,
+                &mut particle_query,
+                center + Vec2::new(angle.cos(), angle.sin()) * distance,
+                Color::srgb(shade, shade * 0.6, shade * 1.5),
+                ParticleKind::Debris {
+                    center,
+                    angle,
+                    distance,
+                    angular_speed: util::random_f32(random.rng.next_u64(), 2.0, 4.0),
+                },
+                Vec2::ZERO,
+                util::random_f32(random.rng.next_u64(), 2.0, 5.0),
+                Duration::from_secs_f32(util::random_f32(random.rng.next_u64(), 0.8, 1.2)),
+            );
+        }
+    }
+}
+
+pub fn update_particles(
+    time: Res<Time>,
+    mut particle_query: ParticleQuery,
+) {
+    let delta = time.delta_secs();
+    for (mut particle, mut sprite, mut transform, mut visibility) in &mut particle_query {
+        if !particle.active {
+            continue;
+        }
+
+        particle.lifetime.tick(time.delta());
+        if particle.lifetime.finished() {
+            particle.deactivate();
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+
+        let progress = particle.progress();
+        let position = match particle.kind {
+            ParticleKind::Droplet => {
+                particle.velocity.y -= DROPLET_GRAVITY * delta;
+                particle.velocity *= 1.0 - (PARTICLE_DRAG * delta).min(1.0);
+                transform.translation.truncate() + particle.velocity * delta
+            }
+            ParticleKind::Spark => {
+                particle.velocity *= 1.0 - (PARTICLE_DRAG * delta).min(1.0);
+                transform.translation.truncate() + particle.velocity * delta
+            }
+            ParticleKind::Debris { center, angle, distance, angular_speed } => {
+                // spin faster the closer the debris gets to the middle
+                let current_angle = angle + angular_speed * particle.lifetime.elapsed_secs() * (1.0 + progress * 2.0);
+                let current_distance = distance * (1.0 - progress);
+                center + Vec2::new(current_angle.cos(), current_angle.sin()) * current_distance
+            }
+        };
+
+        transform.translation = position.extend(400.0);
+        sprite.custom_size = Some(Vec2::splat(particle.size * (1.0 - progress * 0.5)));
+        sprite.color.set_alpha(1.0 - progress);
+    }
+}
+
+// Turns a fractional emission amount into a whole number of particles, carrying
+// the remainder over as a random chance so low rates still emit something
+fn emission_count(random: &mut WyRand, expected: f32) -> u32 {
+    let whole = expected.floor();
+    let extra = if util::random_f32(random.next_u64(), 0.0, 1.0) < expected - whole { 1 } else { 0 };
+    whole as u32 + extra
+}
+
+fn emit_particle(
+    pool: &mut ParticlePool,
+    particle_query: &mut ParticleQuery,
+    position: Vec2,
+    color: Color,
+    kind: ParticleKind,
+    velocity: Vec2,
+    size: f32,
+    lifetime: Duration,
+) {
+    let Some(entity) = pool.next_entity() else {
+        return;
+    };
+    let Ok((mut particle, mut sprite, mut transform, mut visibility)) = particle_query.get_mut(entity) else {
+        return;
+    };
+    particle.activate(kind, velocity, size, lifetime);
+    sprite.color = color;
+    sprite.custom_size = Some(Vec2::splat(size));
+    transform.translation = position.extend(400.0);
+    *visibility = Visibility::Visible;
+}
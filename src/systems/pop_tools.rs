@@ -0,0 +1,238 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use crate::{
+    components::{camera::MainCamera, pop_tools::PopToolIndicator},
+    resources::{cache::MeshCache, input::{ActionState, InputAction}, interaction::{MouseClickEvent, PointerCapture}, pop_tools::*, ui::{UpgradeChangedEvent, UpgradesMenuInfo}},
+    systems::ui::upgrades_menu::UpgradesMenuAction,
+    util::window_to_world_2d,
+};
+
+pub fn pop_tool_selected(tool: PopTool) -> impl Fn(Res<PopTools>) -> bool {
+    move |pop_tools: Res<PopTools>| pop_tools.selected == tool
+}
+
+// Switches tools and lets the upgrades menu know, so the old and new tool
+// buttons can update their labels
+pub fn select_pop_tool(
+    pop_tools: &mut PopTools,
+    unlock_events: &mut EventWriter<UpgradeChangedEvent>,
+    tool: PopTool,
+) {
+    let previous = pop_tools.selected;
+    pop_tools.selected = tool;
+    unlock_events.send(UpgradeChangedEvent { action: UpgradesMenuAction::UnlockTool(previous) });
+    unlock_events.send(UpgradeChangedEvent { action: UpgradesMenuAction::UnlockTool(tool) });
+}
+
+pub fn reset_pop_tools(
+    mut commands: Commands,
+) {
+    commands.insert_resource(PopTools::default());
+    commands.insert_resource(SweepTool::default());
+    commands.insert_resource(ChargeTool::default());
+    commands.insert_resource(AreaPopTool::default());
+}
+
+pub fn cycle_pop_tool(
+    actions: Res<ActionState>,
+    upgrades: Res<UpgradesMenuInfo>,
+    mut pop_tools: ResMut<PopTools>,
+    mut unlock_events: EventWriter<UpgradeChangedEvent>,
+) {
+    if !actions.just_pressed(InputAction::CyclePopTool) {
+        return;
+    }
+
+    let owned = PopTool::all().into_iter()
+        .filter(|tool| upgrades.owns_tool(*tool))
+        .collect::<Vec<PopTool>>();
+    let index = owned.iter().position(|tool| *tool == pop_tools.selected).unwrap_or(0);
+    select_pop_tool(&mut pop_tools, &mut unlock_events, owned[(index + 1) % owned.len()]);
+}
+
+pub fn sweep_pop(
+    time: Res<Time>,
+    actions: Res<ActionState>,
+    mut sweep: ResMut<SweepTool>,
+    mut mouse_click_event: EventWriter<MouseClickEvent>,
+    pointer_capture: Res<PointerCapture>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+) {
+    if !actions.pressed(InputAction::Pop) {
+        sweep.last_position = None;
+        sweep.recharge_delay.tick(time.delta());
+        if sweep.recharge_delay.finished() {
+            sweep.energy = (sweep.energy + sweep.recharge_rate * time.delta_secs()).min(sweep.max_energy);
+        }
+        return;
+    }
+    sweep.recharge_delay.reset();
+
+    // a sweep has to start on the playfield, and ends if it wanders onto the UI
+    let cursor = cursor_world_position(&pointer_capture, &window_query, &camera_query);
+    let Some((position, window_position)) = cursor else {
+        sweep.last_position = None;
+        return;
+    };
+    if sweep.last_position.is_none() && !actions.just_pressed(InputAction::Pop) {
+        return;
+    }
+    let start = sweep.last_position.unwrap_or(position);
+    sweep.last_position = Some(position);
+
+    // the path gets cut short once the energy runs out
+    let travelled = start.distance(position);
+    let length = travelled.min(sweep.energy);
+    sweep.energy -= length;
+    // holding still doesn't pop anything, only the initial press and actual movement do
+    if length <= 0.0 && !actions.just_pressed(InputAction::Pop) {
+        return;
+    }
+
+    let end = if travelled > 0.0 { start + (position - start) * (length / travelled) } else { position };
+    mouse_click_event.send(MouseClickEvent {
+        position: end,
+        path_start: start,
+        window_position,
+        radius: sweep.width / 2.0,
+    });
+}
+
+pub fn charge_pop(
+    time: Res<Time>,
+    actions: Res<ActionState>,
+    mut charge: ResMut<ChargeTool>,
+    mut mouse_click_event: EventWriter<MouseClickEvent>,
+    pointer_capture: Res<PointerCapture>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+) {
+    if actions.just_pressed(InputAction::Pop) {
+        charge.charging = cursor_world_position(&pointer_capture, &window_query, &camera_query).is_some();
+        charge.charge = 0.0;
+    }
+    if !charge.charging {
+        return;
+    }
+
+    if actions.pressed(InputAction::Pop) {
+        charge.charge = (charge.charge + time.delta_secs() / charge.charge_time).min(1.0);
+    } else if actions.just_released(InputAction::Pop) {
+        charge.charging = false;
+        if let Some((position, window_position)) = cursor_world_position(&pointer_capture, &window_query, &camera_query) {
+            mouse_click_event.send(MouseClickEvent {
+                position,
+                path_start: position,
+                window_position,
+                radius: charge.radius(),
+            });
+        }
+    }
+}
+
+pub fn area_pop(
+    actions: Res<ActionState>,
+    mut area: ResMut<AreaPopTool>,
+    mut mouse_click_event: EventWriter<MouseClickEvent>,
+    pointer_capture: Res<PointerCapture>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+) {
+    if !actions.just_pressed(InputAction::Pop) {
+        return;
+    }
+    let Some((position, window_position)) = cursor_world_position(&pointer_capture, &window_query, &camera_query) else {
+        return;
+    };
+
+    let radius = if area.is_ready() {
+        area.cooldown.reset();
+        area.radius
+    } else {
+        0.0
+    };
+    mouse_click_event.send(MouseClickEvent {
+        position,
+        path_start: position,
+        window_position,
+        radius,
+    });
+}
+
+pub fn tick_pop_tool_cooldowns(
+    time: Res<Time>,
+    mut area: ResMut<AreaPopTool>,
+) {
+    area.cooldown.tick(time.delta());
+}
+
+pub fn spawn_pop_tool_indicator(
+    mut commands: Commands,
+    mesh_cache: Res<MeshCache>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    commands.spawn((
+        Mesh2d(mesh_cache.circle_mesh.clone()),
+        MeshMaterial2d(materials.add(Color::srgba(1.0, 1.0, 1.0, 0.2))),
+        Transform::from_translation(Vec3::new(0.0, 0.0, 600.0)),
+        Visibility::Hidden,
+        PopToolIndicator,
+    ));
+}
+
+pub fn cleanup_pop_tool_indicator(
+    mut commands: Commands,
+    query: Query<Entity, With<PopToolIndicator>>,
+) {
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+// Shows how far the selected tool reaches: the sweep's width while it has
+// energy, the charge as it builds up and the area pop while it's ready
+pub fn update_pop_tool_indicator(
+    pop_tools: Res<PopTools>,
+    sweep: Res<SweepTool>,
+    charge: Res<ChargeTool>,
+    area: Res<AreaPopTool>,
+    pointer_capture: Res<PointerCapture>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    mut indicator_query: Query<(&mut Transform, &mut Visibility), With<PopToolIndicator>>,
+) {
+    let cursor = cursor_world_position(&pointer_capture, &window_query, &camera_query);
+    let radius = match pop_tools.selected {
+        PopTool::Sweep if sweep.energy > 0.0 => Some(sweep.width / 2.0),
+        PopTool::Charge if charge.charging => Some(charge.radius()),
+        PopTool::Area if area.is_ready() => Some(area.radius),
+        _ => None,
+    };
+
+    for (mut transform, mut visibility) in &mut indicator_query {
+        let (Some((position, _)), Some(radius)) = (cursor, radius) else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        transform.translation = position.extend(600.0);
+        transform.scale = Vec3::splat(radius.max(4.0));
+        *visibility = Visibility::Visible;
+    }
+}
+
+// The cursor in world and window space, unless it's over the UI
+fn cursor_world_position(
+    pointer_capture: &PointerCapture,
+    window_query: &Query<&Window, With<PrimaryWindow>>,
+    camera_query: &Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+) -> Option<(Vec2, Vec2)> {
+    let window = window_query.get_single().ok()?;
+    let (camera, camera_transform) = camera_query.get_single().ok()?;
+    let window_position = window.cursor_position()?;
+    if pointer_capture.blocks_mouse(window_position) {
+        return None;
+    }
+    let position = window_to_world_2d(camera, camera_transform, window_position)?;
+    Some((position, window_position))
+}
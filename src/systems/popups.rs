@@ -0,0 +1,129 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::{components::popups::ScorePopup, resources::{bubbles::BubbleDestroyedEvent, cache::FontCache, popups::ScorePopupPool}};
+
+pub fn init_score_popup_pool(
+    mut commands: Commands,
+    mut pool: ResMut<ScorePopupPool>,
+    font_cache: Res<FontCache>,
+) {
+    pool.reset();
+    for _ in 0..pool.pool_size {
+        let entity = commands.spawn((
+            Text2d::new(""),
+            TextFont {
+                font: font_cache.coolvetica_rg.clone(),
+                font_size: 24.0,
+                ..default()
+            },
+            TextColor(Color::WHITE),
+            TextLayout::new_with_justify(JustifyText::Center),
+            Transform::default(),
+            Visibility::Hidden,
+            ScorePopup::default(),
+        )).id();
+        pool.entities.push(entity);
+    }
+}
+
+pub fn cleanup_score_popups(
+    mut commands: Commands,
+    mut pool: ResMut<ScorePopupPool>,
+    query: Query<Entity, With<ScorePopup>>,
+) {
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
+    }
+    pool.reset();
+}
+
+pub fn spawn_score_popups(
+    time: Res<Time>,
+    mut pool: ResMut<ScorePopupPool>,
+    mut bubble_destroyed_event: EventReader<BubbleDestroyedEvent>,
+    mut popup_query: Query<(&mut ScorePopup, &mut Text2d, &mut Visibility)>,
+) {
+    pool.refill(time.delta().as_secs_f32());
+
+    for event in bubble_destroyed_event.read() {
+        // user pops don't score, so they don't get a popup either
+        if event.triggered_by_user {
+            continue;
+        }
+
+        let mut nearest_fresh = None;
+        let mut nearest_any = None;
+        let mut free = None;
+        for &entity in &pool.entities {
+            let Ok((popup, _, _)) = popup_query.get(entity) else {
+                continue;
+            };
+            if !popup.active {
+                free = free.or(Some(entity));
+                continue;
+            }
+            let distance = popup.origin.distance_squared(event.position);
+            if nearest_any.is_none_or(|(_, best)| distance < best) {
+                nearest_any = Some((entity, distance));
+            }
+            if popup.progress() < 0.5
+                && distance < pool.merge_radius.powi(2)
+                && nearest_fresh.is_none_or(|(_, best)| distance < best) {
+                nearest_fresh = Some((entity, distance));
+            }
+        }
+
+        // prefer merging into a popup that's part of the same chain, then a new
+        // popup if the rate limit allows it, and otherwise just fold the pop into
+        // whatever popup is closest so the counts still add up
+        let target = if let Some((entity, _)) = nearest_fresh {
+            Some((entity, false))
+        } else if let Some(entity) = free.filter(|_| pool.try_spawn()) {
+            Some((entity, true))
+        } else {
+            nearest_any.map(|(entity, _)| (entity, false))
+        };
+
+        let Some((entity, is_new)) = target else {
+            continue;
+        };
+        let Ok((mut popup, mut text, mut visibility)) = popup_query.get_mut(entity) else {
+            continue;
+        };
+        if is_new {
+            popup.activate(event.position, 1, Duration::from_secs_f32(pool.lifetime));
+            *visibility = Visibility::Visible;
+        } else {
+            popup.add(1);
+        }
+        text.0 = popup.label(pool.chain_threshold);
+    }
+}
+
+pub fn animate_score_popups(
+    time: Res<Time>,
+    pool: Res<ScorePopupPool>,
+    mut popup_query: Query<(&mut ScorePopup, &mut Transform, &mut TextColor, &mut Visibility)>,
+) {
+    for (mut popup, mut transform, mut text_color, mut visibility) in &mut popup_query {
+        if !popup.active {
+            continue;
+        }
+
+        popup.lifetime.tick(time.delta());
+        if popup.lifetime.finished() {
+            popup.deactivate();
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+
+        let progress = popup.progress();
+        let rise = pool.rise_distance * (1.0 - (1.0 - progress).powi(2));
+        // draw above every bubble, which sit at z = -(x / 1000 + y)
+        transform.translation = (popup.origin + Vec2::new(0.0, rise)).extend(500.0);
+        transform.scale = Vec3::splat(1.0 + (popup.count as f32).log10() * 0.5);
+        text_color.0.set_alpha((1.0 - progress).powf(0.5));
+    }
+}
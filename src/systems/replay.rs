@@ -0,0 +1,138 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, time::TimeUpdateStrategy, utils::HashMap};
+
+use crate::{
+    game_states::GameState,
+    persistence,
+    resources::{bubbles::{BubbleChances, BubbleSpawnTimer}, interaction::MouseClickEvent, replay::*, run::{GameMode, RunSeed}, stats::GameStats, ui::{UpgradeChangedEvent, UpgradesMenuInfo}},
+    systems::ui::upgrades_menu::purchase_upgrade,
+};
+
+pub fn replaying(
+    playback: Option<Res<ReplayPlayback>>,
+) -> bool {
+    playback.is_some()
+}
+
+pub fn start_recording(
+    mut commands: Commands,
+    run_seed: Res<RunSeed>,
+    mode: Res<GameMode>,
+) {
+    commands.insert_resource(ReplayRecorder {
+        replay: Replay {
+            seed: run_seed.current,
+            mode: *mode,
+            frames: Vec::new(),
+        },
+        // upgrades get reset as the run starts, so nothing has been bought yet
+        purchases: HashMap::default(),
+    });
+}
+
+pub fn record_replay_frame(
+    time: Res<Time<Real>>,
+    mut recorder: ResMut<ReplayRecorder>,
+    upgrades: Res<UpgradesMenuInfo>,
+    mut click_events: EventReader<MouseClickEvent>,
+) {
+    let pops = click_events.read().map(ReplayPop::from_event).collect();
+    let mut purchases = Vec::new();
+    for (action, count) in &upgrades.purchases {
+        let previous = recorder.purchases.insert(*action, *count).unwrap_or_default();
+        if *count > previous {
+            purchases.push((*action, count - previous));
+        }
+    }
+    recorder.replay.frames.push(ReplayFrame {
+        delta: time.delta().as_secs_f64(),
+        pops,
+        purchases,
+    });
+}
+
+pub fn save_recording(
+    mut commands: Commands,
+    recorder: Option<Res<ReplayRecorder>>,
+) {
+    let Some(recorder) = recorder else {
+        return;
+    };
+    persistence::save(Replay::LAST_RUN_KEY, &recorder.replay);
+    commands.remove_resource::<ReplayRecorder>();
+    commands.insert_resource(RecordedReplay);
+}
+
+pub fn forget_recorded_replay(
+    mut commands: Commands,
+) {
+    commands.remove_resource::<RecordedReplay>();
+}
+
+// Has to land before `TimeSystem` so this frame runs with the recorded delta
+pub fn set_replay_time(
+    mut commands: Commands,
+    playback: Res<ReplayPlayback>,
+) {
+    if let Some(frame) = playback.current_frame() {
+        commands.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(frame.delta)));
+    }
+}
+
+pub fn play_replay_pops(
+    playback: Res<ReplayPlayback>,
+    mut click_events: EventWriter<MouseClickEvent>,
+) {
+    let Some(frame) = playback.current_frame() else {
+        return;
+    };
+    for pop in &frame.pops {
+        click_events.send(pop.to_event());
+    }
+}
+
+pub fn play_replay_purchases(
+    playback: Res<ReplayPlayback>,
+    mut spawn_timer: ResMut<BubbleSpawnTimer>,
+    mut spawn_chances: ResMut<BubbleChances>,
+    mut upgrades: ResMut<UpgradesMenuInfo>,
+    mut stats: ResMut<GameStats>,
+    mut unlock_events: EventWriter<UpgradeChangedEvent>,
+) {
+    let Some(frame) = playback.current_frame() else {
+        return;
+    };
+    for (action, amount) in &frame.purchases {
+        purchase_upgrade(
+            &mut spawn_timer,
+            &mut spawn_chances,
+            &mut upgrades,
+            &mut stats,
+            &mut unlock_events,
+            *action,
+            *amount,
+        );
+    }
+}
+
+pub fn advance_replay(
+    mut commands: Commands,
+    mut playback: ResMut<ReplayPlayback>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    mut app_exit: EventWriter<AppExit>,
+) {
+    playback.frame += 1;
+    if playback.current_frame().is_some() {
+        return;
+    }
+
+    info!("Replay finished after {} frames", playback.frame);
+    commands.remove_resource::<ReplayPlayback>();
+    commands.insert_resource(TimeUpdateStrategy::Automatic);
+    if playback.exit_when_done {
+        app_exit.send(AppExit::Success);
+    } else {
+        next_game_state.set(GameState::MainMenu);
+    }
+}
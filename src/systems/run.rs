@@ -0,0 +1,108 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, time::TimeUpdateStrategy};
+use bevy_rand::prelude::{GlobalEntropy, WyRand};
+use rand_core::RngCore;
+
+use crate::{
+    cli::LaunchOptions,
+    game_states::GameState,
+    resources::{replay::{Replay, ReplayPlayback}, run::*},
+};
+
+// Headless runs step time by a fixed amount, so they come out the same however
+// fast the machine is
+const HEADLESS_TICK: f64 = 1.0 / 60.0;
+
+pub fn apply_launch_options(
+    mut commands: Commands,
+    options: Option<Res<LaunchOptions>>,
+    mut mode: ResMut<GameMode>,
+    mut run_seed: ResMut<RunSeed>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+) {
+    let Some(options) = options else {
+        return;
+    };
+
+    if let Some(selected) = options.mode {
+        *mode = selected;
+    }
+    // anything already lined up (like the benchmark's seed) stays unless overridden
+    if let Some(seed) = options.seed {
+        run_seed.next = Some(seed);
+    }
+    if options.skips_menu() {
+        next_game_state.set(GameState::InGame);
+    }
+    if options.headless && !options.benchmark {
+        let tick = Duration::from_secs_f64(HEADLESS_TICK);
+        commands.insert_resource(TimeUpdateStrategy::ManualDuration(tick));
+    }
+    // benchmarks count their own ticks
+    if let Some(ticks) = options.ticks.filter(|_| !options.benchmark) {
+        commands.insert_resource(TickLimit { remaining: ticks });
+    }
+
+    if let Some(path) = &options.replay {
+        match load_replay(path) {
+            Ok(replay) => {
+                *mode = replay.mode;
+                run_seed.next = Some(replay.seed);
+                commands.insert_resource(ReplayPlayback {
+                    replay,
+                    frame: 0,
+                    exit_when_done: options.runs_headless(),
+                });
+            }
+            Err(error) => warn!("Failed to load replay {}: {}", path.display(), error),
+        }
+    }
+}
+
+fn load_replay(path: &std::path::Path) -> Result<Replay, String> {
+    let data = std::fs::read_to_string(path).map_err(|error| error.to_string())?;
+    ron::from_str(&data).map_err(|error| error.to_string())
+}
+
+// Reseeds the rng before anything else in the run gets to use it
+pub fn start_run(
+    mut random: ResMut<GlobalEntropy<WyRand>>,
+    mut run_seed: ResMut<RunSeed>,
+    mut run_timer: ResMut<RunTimer>,
+    mut tainted: ResMut<RunTainted>,
+) {
+    let seed = run_seed.next.take().unwrap_or_else(|| random.next_u64());
+    random.reseed(seed.to_le_bytes());
+    run_seed.current = seed;
+    *run_timer = RunTimer::default();
+    *tainted = RunTainted::default();
+}
+
+pub fn run_untainted(
+    tainted: Option<Res<RunTainted>>,
+) -> bool {
+    !tainted.is_some_and(|tainted| tainted.0)
+}
+
+pub fn tick_run_timer(
+    time: Res<Time>,
+    mode: Res<GameMode>,
+    mut run_timer: ResMut<RunTimer>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+) {
+    run_timer.elapsed += time.delta_secs();
+    if run_timer.remaining(*mode) == Some(0.0) {
+        next_game_state.set(GameState::Summary);
+    }
+}
+
+pub fn count_down_tick_limit(
+    mut tick_limit: ResMut<TickLimit>,
+    mut app_exit: EventWriter<AppExit>,
+) {
+    tick_limit.remaining = tick_limit.remaining.saturating_sub(1);
+    if tick_limit.remaining == 0 {
+        app_exit.send(AppExit::Success);
+    }
+}
@@ -0,0 +1,201 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy_rand::prelude::{GlobalEntropy, WyRand};
+
+use crate::{
+    cli::LaunchOptions,
+    components::{bubbles::*, physics::{Collider, Velocity}},
+    persistence,
+    resources::{benchmark::Benchmark, bubbles::{BubbleChances, BubbleSpawnTimer}, cache::MeshCache, replay::ReplayPlayback, run::{GameMode, RunSeed, RunTainted, RunTimer}, save::*, stats::{GameStats, RunStats}, ui::UpgradesMenuInfo},
+    util::ActionTimer,
+};
+
+// The run-wide resources that make up a save
+#[derive(SystemParam)]
+pub struct RunResources<'w> {
+    mode: Res<'w, GameMode>,
+    run_seed: ResMut<'w, RunSeed>,
+    run_timer: ResMut<'w, RunTimer>,
+    tainted: ResMut<'w, RunTainted>,
+    stats: ResMut<'w, GameStats>,
+    run_stats: ResMut<'w, RunStats>,
+    upgrades: ResMut<'w, UpgradesMenuInfo>,
+    chances: ResMut<'w, BubbleChances>,
+    spawn_timer: ResMut<'w, BubbleSpawnTimer>,
+    random: ResMut<'w, GlobalEntropy<WyRand>>,
+}
+
+#[derive(SystemParam)]
+pub struct RunEntities<'w, 's> {
+    materials: Res<'w, Assets<ColorMaterial>>,
+    bubbles: Query<'w, 's, (&'static Transform, &'static Bubble, &'static Velocity, &'static Collider, &'static MeshMaterial2d<ColorMaterial>)>,
+    shockwaves: Query<'w, 's, (&'static Transform, &'static TimedEffect, &'static BubbleShockwave, &'static Collider, &'static MeshMaterial2d<ColorMaterial>)>,
+    black_holes: Query<'w, 's, (&'static Transform, &'static TimedEffect, &'static BubbleBlackHole, &'static Collider, &'static MeshMaterial2d<ColorMaterial>)>,
+    beams: Query<'w, 's, (&'static Transform, &'static TimedEffect, &'static BubbleBeam, &'static MeshMaterial2d<ColorMaterial>)>,
+    scatter_shots: Query<'w, 's, (&'static Transform, &'static ActionTimer, &'static BubbleScatterShotSpawner)>,
+}
+
+impl RunEntities<'_, '_> {
+    fn color(&self, material: &MeshMaterial2d<ColorMaterial>) -> Color {
+        self.materials.get(material).map(|material| material.color).unwrap_or(Color::WHITE)
+    }
+}
+
+// Benchmarks, replays and headless runs aren't the player's, so they'd only
+// overwrite a run worth keeping
+pub fn autosaving(
+    options: Option<Res<LaunchOptions>>,
+    benchmark: Option<Res<Benchmark>>,
+    playback: Option<Res<ReplayPlayback>>,
+) -> bool {
+    benchmark.is_none() && playback.is_none() && !options.is_some_and(|options| options.runs_headless())
+}
+
+pub fn resuming_run(
+    resume: Option<Res<ResumeRun>>,
+) -> bool {
+    resume.is_some()
+}
+
+pub fn save_run(
+    resources: RunResources,
+    entities: RunEntities,
+) {
+    let upgrades = &resources.upgrades;
+    let saved_run = SavedRun {
+        mode: *resources.mode,
+        seed: resources.run_seed.current,
+        elapsed: resources.run_timer.elapsed,
+        score: resources.stats.score,
+        upgrades: SavedUpgrades {
+            unlocked: upgrades.unlocked.clone(),
+            costs: upgrades.costs.iter().map(|(action, cost)| (*action, *cost)).collect(),
+            auto_buy: upgrades.auto_buy.iter().copied().collect(),
+            purchases: upgrades.purchases.iter().map(|(action, count)| (*action, *count)).collect(),
+        },
+        chances: resources.chances.chances.iter().map(|(bubble_type, chance)| (*bubble_type, *chance)).collect(),
+        spawn_timer: resources.spawn_timer.action_timer.clone(),
+        random: resources.random.clone(),
+        bubbles: entities.bubbles.iter()
+            .map(|(transform, bubble, velocity, collider, material)| SavedBubble {
+                transform: *transform,
+                bubble: bubble.clone(),
+                velocity: velocity.velocity,
+                collider: collider.clone(),
+                color: entities.color(material),
+            })
+            .collect(),
+        shockwaves: entities.shockwaves.iter()
+            .map(|(transform, timed_effect, shockwave, collider, material)| SavedShockwave {
+                transform: *transform,
+                timed_effect: timed_effect.clone(),
+                shockwave: shockwave.clone(),
+                collider: collider.clone(),
+                color: entities.color(material),
+            })
+            .collect(),
+        black_holes: entities.black_holes.iter()
+            .map(|(transform, timed_effect, black_hole, collider, material)| SavedBlackHole {
+                transform: *transform,
+                timed_effect: timed_effect.clone(),
+                black_hole: black_hole.clone(),
+                collider: collider.clone(),
+                color: entities.color(material),
+            })
+            .collect(),
+        beams: entities.beams.iter()
+            .map(|(transform, timed_effect, beam, material)| SavedBeam {
+                transform: *transform,
+                timed_effect: timed_effect.clone(),
+                beam: beam.clone(),
+                color: entities.color(material),
+            })
+            .collect(),
+        scatter_shots: entities.scatter_shots.iter()
+            .map(|(transform, action_timer, spawner)| SavedScatterShot {
+                transform: *transform,
+                action_timer: action_timer.clone(),
+                spawner: spawner.clone(),
+            })
+            .collect(),
+        stats: resources.run_stats.clone(),
+        tainted: resources.tainted.0,
+    };
+    persistence::save(SavedRun::SAVE_KEY, &saved_run);
+}
+
+// Runs once the fresh run is set up, then overwrites it with the saved one
+pub fn restore_run(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mesh_cache: Res<MeshCache>,
+    resume: Res<ResumeRun>,
+    mut resources: RunResources,
+) {
+    let saved_run = &resume.0;
+    resources.run_seed.current = saved_run.seed;
+    resources.run_timer.elapsed = saved_run.elapsed;
+    resources.tainted.0 = saved_run.tainted;
+    resources.stats.score = saved_run.score;
+    *resources.run_stats = saved_run.stats.clone();
+    resources.upgrades.unlocked = saved_run.upgrades.unlocked.clone();
+    resources.upgrades.costs = saved_run.upgrades.costs.iter().copied().collect();
+    resources.upgrades.auto_buy = saved_run.upgrades.auto_buy.iter().copied().collect();
+    resources.upgrades.purchases = saved_run.upgrades.purchases.iter().copied().collect();
+    resources.chances.chances = saved_run.chances.iter().copied().collect();
+    resources.spawn_timer.action_timer = saved_run.spawn_timer.clone();
+    *resources.random = saved_run.random.clone();
+
+    for saved in &saved_run.bubbles {
+        commands.spawn(BubbleBundle {
+            mesh: Mesh2d(mesh_cache.circle_mesh.clone()),
+            mesh_material: MeshMaterial2d(materials.add(saved.color)),
+            transform: saved.transform,
+            bubble: saved.bubble.clone(),
+            velocity: Velocity { velocity: saved.velocity },
+            collider: saved.collider.clone(),
+        });
+    }
+    for saved in &saved_run.shockwaves {
+        commands.spawn(BubbleShockwaveBundle {
+            mesh: Mesh2d(mesh_cache.circle_mesh.clone()),
+            mesh_material: MeshMaterial2d(materials.add(saved.color)),
+            transform: saved.transform,
+            timed_effect: saved.timed_effect.clone(),
+            bubble_shockwave: saved.shockwave.clone(),
+            collider: saved.collider.clone(),
+        });
+    }
+    for saved in &saved_run.black_holes {
+        commands.spawn(BubbleBlackHoleBundle {
+            mesh: Mesh2d(mesh_cache.circle_mesh.clone()),
+            mesh_material: MeshMaterial2d(materials.add(saved.color)),
+            transform: saved.transform,
+            timed_effect: saved.timed_effect.clone(),
+            bubble_black_hole: saved.black_hole.clone(),
+            collider: saved.collider.clone(),
+        });
+    }
+    for saved in &saved_run.beams {
+        commands.spawn(BubbleBeamBundle {
+            mesh: Mesh2d(mesh_cache.long_rectangle_mesh.clone()),
+            mesh_material: MeshMaterial2d(materials.add(saved.color)),
+            timed_effect: saved.timed_effect.clone(),
+            beam: saved.beam.clone(),
+            transform: saved.transform,
+        });
+    }
+    for saved in &saved_run.scatter_shots {
+        commands.spawn(BubbleScatterShotSpawnerBundle {
+            action_timer: saved.action_timer.clone(),
+            spawner: saved.spawner.clone(),
+            transform: saved.transform,
+        });
+    }
+
+    commands.remove_resource::<ResumeRun>();
+}
+
+// A run that has ended can't be continued
+pub fn discard_saved_run() {
+    persistence::remove(SavedRun::SAVE_KEY);
+}
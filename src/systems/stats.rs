@@ -1,15 +1,86 @@
-use bevy::{prelude::*, window::PrimaryWindow};
+use bevy::prelude::*;
 
-use crate::{components::ui::ScoreText, resources::{bubbles::BubbleCollapsedEvent, cache::FontCache, stats::GameStats, ui::{UpgradeChangedEvent, UpgradesMenuInfo}}, util::get_viewport_bounds};
+use crate::{components::ui::ScoreText, persistence, resources::{bubbles::{BubbleCollapsedEvent, BubbleDestroyedEvent}, cache::FontCache, camera::Playfield, run::{GameMode, RunSeed, RunTimer}, stats::{Chain, GameStats, RunHistory, RunRecord, RunStats}, ui::{UpgradeChangedEvent, UpgradesMenuInfo}}, util};
 
-pub fn init_stats(mut game_stats: ResMut<GameStats>) {
+pub fn init_stats(
+    mut game_stats: ResMut<GameStats>,
+    mut chain: ResMut<Chain>,
+    mut run_stats: ResMut<RunStats>,
+) {
     game_stats.score = 0;
+    *chain = Chain::default();
+    *run_stats = RunStats::default();
+}
+
+pub fn track_chain(
+    time: Res<Time>,
+    mut chain: ResMut<Chain>,
+    mut bubble_collapse_event: EventReader<BubbleCollapsedEvent>,
+) {
+    for _ in bubble_collapse_event.read() {
+        chain.extend();
+    }
+    chain.tick(time.delta());
+}
+
+pub fn track_run_stats(
+    time: Res<Time>,
+    chain: Res<Chain>,
+    mut run_stats: ResMut<RunStats>,
+    mut bubble_collapse_event: EventReader<BubbleCollapsedEvent>,
+    mut bubble_destroyed_event: EventReader<BubbleDestroyedEvent>,
+) {
+    let mut pops = 0;
+    for collapse in bubble_collapse_event.read() {
+        pops += 1;
+        if collapse.triggered_by_user {
+            run_stats.user_pops += 1;
+        } else {
+            run_stats.effect_pops += 1;
+        }
+        if let Some(source) = collapse.source {
+            *run_stats.score_by_source.entry(source).or_default() += collapse.score_change;
+        }
+    }
+    for destroyed in bubble_destroyed_event.read() {
+        *run_stats.popped.entry(destroyed.bubble_type).or_default() += 1;
+    }
+    run_stats.longest_chain = run_stats.longest_chain.max(chain.length);
+    run_stats.record_pops(pops, time.delta_secs());
+}
+
+// Fills in what's only known once the run is over
+pub fn finish_run_stats(
+    run_timer: Res<RunTimer>,
+    upgrades: Res<UpgradesMenuInfo>,
+    mut run_stats: ResMut<RunStats>,
+) {
+    run_stats.time_alive = run_timer.elapsed;
+    run_stats.upgrades_bought = upgrades.purchases.clone();
+}
+
+pub fn record_run_history(
+    game_stats: Res<GameStats>,
+    mode: Res<GameMode>,
+    run_seed: Res<RunSeed>,
+    run_stats: Res<RunStats>,
+) {
+    let mut history: RunHistory = persistence::load(RunHistory::SAVE_KEY).unwrap_or_default();
+    history.push(RunRecord {
+        date: util::unix_time(),
+        mode: *mode,
+        seed: run_seed.current,
+        score: game_stats.score,
+        stats: run_stats.clone(),
+    });
+    persistence::save(RunHistory::SAVE_KEY, &history);
 }
 
 pub fn draw_score(
     mut commands: Commands,
     game_stats: Res<GameStats>,
     font_cache: Res<FontCache>,
+    playfield: Res<Playfield>,
 ) {
     let text_font = TextFont {
         font: font_cache.coolvetica_rg.clone(),
@@ -21,7 +92,7 @@ pub fn draw_score(
         text_font,
         TextLayout::new_with_justify(JustifyText::Center),
         ScoreText::default(),
-        Transform::from_translation(Vec3::new(-400.0, 300.0, 0.0)),
+        Transform::from_translation(score_position(&playfield)),
     ));
 }
 
@@ -35,26 +106,34 @@ pub fn update_score(
     mut commands: Commands,
     time: Res<Time>,
     game_stats: Res<GameStats>,
+    mode: Res<GameMode>,
+    run_timer: Res<RunTimer>,
     mut score_query: Query<(Entity, &mut Transform, &mut ScoreText)>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    camera_query: Query<(&Camera, &GlobalTransform)>,
+    playfield: Res<Playfield>,
 ) {
-    let viewport_bounds = get_viewport_bounds(&window_query, &camera_query);
-    
-    let position = viewport_bounds.map(|bounds| Vec3::new(bounds.min.x + 200.0, bounds.max.y - 100.0, 0.0));
+    let position = score_position(&playfield);
+    let mut text = format!("Score: {}", game_stats.score);
+    // timed modes count down next to the score
+    if let Some(remaining) = run_timer.remaining(*mode) {
+        let seconds = remaining.ceil() as u32;
+        text = format!("{}  {}:{:02}", text, seconds / 60, seconds % 60);
+    }
 
     for (entity, mut transform, mut score_text) in &mut score_query {
-        commands.entity(entity).insert(Text2d::new(format!("Score: {}", game_stats.score)));
+        commands.entity(entity).insert(Text2d::new(text.clone()));
         score_text.scale_timer.tick(time.delta());
         let left = score_text.scale_timer.remaining_secs() / score_text.scale_timer.duration().as_secs_f32();
         let scale = 1.0 + 0.2 * left;
         transform.scale = Vec3::new(scale, scale, 1.0);
-        if let Some(position) = position {
-            transform.translation = position;
-        }
+        transform.translation = position;
     }
 }
 
+fn score_position(playfield: &Playfield) -> Vec3 {
+    let bounds = playfield.bounds();
+    Vec3::new(bounds.min.x + 200.0, bounds.max.y - 100.0, 0.0)
+}
+
 pub fn increment_score_for_destroyed_bubbles(
     mut game_stats: ResMut<GameStats>,
     mut bubble_collapse_event: EventReader<BubbleCollapsedEvent>,
@@ -1,4 +1,8 @@
+pub mod achievements;
 pub mod debug;
+pub mod leaderboard;
 pub mod main_menu;
+pub mod options_menu;
 pub mod pause_menu;
+pub mod summary;
 pub mod upgrades_menu;
@@ -0,0 +1,64 @@
+use bevy::prelude::*;
+
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{game_states::GameState, resources::achievements::{Achievement, AchievementToasts, UnlockedAchievements}, util};
+
+pub fn ui_achievements(
+    mut contexts: EguiContexts,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    unlocked: Res<UnlockedAchievements>,
+) {
+    egui::Window::new("Achievements")
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .collapsible(false)
+        .resizable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.label(format!("{} of {} unlocked", unlocked.count(), Achievement::all().len()));
+            ui.separator();
+            egui::Grid::new("achievements").striped(true).spacing([20.0, 8.0]).show(ui, |ui| {
+                for achievement in Achievement::all() {
+                    let unlocked_at = unlocked.unlocked_at(achievement);
+                    // locked ones are greyed out but still say how to get them
+                    let color = if unlocked_at.is_some() {
+                        egui::Color32::WHITE
+                    } else {
+                        egui::Color32::DARK_GRAY
+                    };
+                    ui.vertical(|ui| {
+                        ui.label(egui::RichText::new(achievement.name()).strong().color(color));
+                        ui.label(egui::RichText::new(achievement.description()).color(color));
+                    });
+                    ui.label(unlocked_at.map(util::format_date).unwrap_or_else(|| "Locked".to_string()));
+                    ui.end_row();
+                }
+            });
+            ui.separator();
+            if ui.button("Back").clicked() {
+                next_game_state.set(GameState::MainMenu);
+            }
+        });
+}
+
+pub fn ui_achievement_toasts(
+    mut contexts: EguiContexts,
+    toasts: Res<AchievementToasts>,
+) {
+    let Some(achievement) = toasts.queue.front() else {
+        return;
+    };
+    // headless runs have nothing to draw into
+    let Some(ctx) = contexts.try_ctx_mut() else {
+        return;
+    };
+    egui::Area::new(egui::Id::new("achievement_toast"))
+        .anchor(egui::Align2::CENTER_TOP, [0.0, 20.0])
+        .interactable(false)
+        .show(ctx, |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                ui.label(egui::RichText::new("Achievement unlocked").small());
+                ui.label(egui::RichText::new(achievement.name()).heading());
+                ui.label(achievement.description());
+            });
+        });
+}
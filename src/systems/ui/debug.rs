@@ -1,35 +1,242 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, window::PrimaryWindow};
 
 use bevy_egui::{egui, EguiContexts};
 
-use crate::{game_states::DebugState, resources::bubbles};
+use crate::{
+    components::{bubbles::*, camera::MainCamera},
+    game_states::DebugState,
+    resources::{bubbles, cache::MeshCache, debug::{DebugTools, PerfStats, FRAME_HISTORY}, input::{ActionState, InputAction}, interaction::PointerCapture, run::RunTainted, stats, ui::{UpgradeChangedEvent, UpgradesMenuInfo}},
+    systems::{bubbles::BubbleSystemSet, debug::EntityCounts, ui::upgrades_menu::UpgradesMenuAction},
+    util::window_to_world_2d,
+};
 
 pub fn ui_debug(
     mut contexts: EguiContexts,
-    // mut stats: ResMut<stats::GameStats>,
+    mut stats: ResMut<stats::GameStats>,
     mut bubble_spawn_timer: ResMut<bubbles::BubbleSpawnTimer>,
-    // mut bubble_chances: ResMut<bubbles::BubbleChances>,
+    mut bubble_chances: ResMut<bubbles::BubbleChances>,
+    mut upgrades: ResMut<UpgradesMenuInfo>,
+    mut unlock_events: EventWriter<UpgradeChangedEvent>,
+    mut debug_tools: ResMut<DebugTools>,
+    mut tainted: ResMut<RunTainted>,
+    entity_counts: EntityCounts,
 ) {
     egui::Window::new("Debug").show(contexts.ctx_mut(), |ui| {
-        let spawn_rate_label = ui.label("Spawn Rate");
-        ui.add(egui::DragValue::new(&mut bubble_spawn_timer.action_timer.amount)).labelled_by(spawn_rate_label.id);
+        egui::Grid::new("debug_values").show(ui, |ui| {
+            ui.label("Score");
+            if ui.add(egui::DragValue::new(&mut stats.score)).changed() {
+                tainted.0 = true;
+            }
+            ui.end_row();
+            ui.label("Spawn Rate");
+            if ui.add(egui::DragValue::new(&mut bubble_spawn_timer.action_timer.amount)).changed() {
+                tainted.0 = true;
+            }
+            ui.end_row();
+            for bubble_type in BubbleType::all() {
+                let mut chance = bubble_chances.get_chance(bubble_type);
+                ui.label(format!("{} Chance", bubble_type.name()));
+                if ui.add(egui::DragValue::new(&mut chance).speed(0.1).range(0.0..=f32::MAX)).changed() {
+                    bubble_chances.set_chance(bubble_type, chance);
+                    tainted.0 = true;
+                }
+                ui.end_row();
+            }
+        });
+        if ui.button("Unlock All Upgrades").clicked() {
+            for action in UpgradesMenuAction::all() {
+                if !upgrades.is_unlocked(action) {
+                    upgrades.unlock(action);
+                    unlock_events.send(UpgradeChangedEvent { action });
+                    tainted.0 = true;
+                }
+            }
+        }
+
+        ui.separator();
+        if ui.add(egui::Slider::new(&mut debug_tools.time_scale, 0.1..=4.0).logarithmic(true).text("Time Scale")).changed() {
+            tainted.0 = true;
+        }
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut debug_tools.frozen, "Freeze");
+            if ui.add_enabled(debug_tools.frozen, egui::Button::new("Step")).clicked() {
+                debug_tools.step_requested = true;
+            }
+        });
+
+        ui.separator();
+        ui.checkbox(&mut debug_tools.show_gizmos, "Show collision gizmos");
+        ui.checkbox(&mut debug_tools.show_perf_hud, "Show performance");
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut debug_tools.spawn_on_click, "Click to spawn");
+            let spawn_type = &mut debug_tools.spawn_type;
+            egui::ComboBox::from_id_salt("debug_spawn_type")
+                .selected_text(spawn_type.name())
+                .show_ui(ui, |ui| {
+                    for bubble_type in BubbleType::all() {
+                        ui.selectable_value(spawn_type, bubble_type, bubble_type.name());
+                    }
+                });
+        });
+
+        ui.separator();
+        egui::Grid::new("debug_entity_counts").striped(true).show(ui, |ui| {
+            for (name, count) in entity_counts.rows() {
+                ui.label(name);
+                ui.label(count.to_string());
+                ui.end_row();
+            }
+        });
     });
 }
 
-pub fn toggle_debug_on_on_backslash(
+pub fn ui_perf_hud(
+    mut contexts: EguiContexts,
+    mut debug_tools: ResMut<DebugTools>,
+    perf_stats: Res<PerfStats>,
+    entity_counts: EntityCounts,
+) {
+    if !debug_tools.show_perf_hud {
+        return;
+    }
+    egui::Window::new("Performance")
+        .anchor(egui::Align2::RIGHT_TOP, [-10.0, 10.0])
+        .resizable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.label(format!(
+                "{:.0} FPS, {:.2} ms",
+                perf_stats.average_fps(),
+                perf_stats.last_frame_time() * 1000.0,
+            ));
+            draw_frame_time_graph(ui, &perf_stats);
+
+            egui::Grid::new("perf_counts").striped(true).show(ui, |ui| {
+                for (name, count) in entity_counts.rows() {
+                    ui.label(name);
+                    ui.label(count.to_string());
+                    ui.end_row();
+                }
+                for set in BubbleSystemSet::all() {
+                    ui.label(set.name());
+                    ui.label(format!("{:.3} ms", perf_stats.set_time(set).as_secs_f64() * 1000.0));
+                    ui.end_row();
+                }
+            });
+
+            #[cfg(not(target_arch = "wasm32"))]
+            ui.checkbox(&mut debug_tools.record_csv, "Record CSV");
+        });
+}
+
+// Frame times over the last few seconds, scaled so 30 FPS is the top unless
+// something took longer. The line marks 60 FPS
+fn draw_frame_time_graph(ui: &mut egui::Ui, perf_stats: &PerfStats) {
+    let (response, painter) = ui.allocate_painter(egui::vec2(240.0, 60.0), egui::Sense::hover());
+    let rect = response.rect;
+    painter.rect_filled(rect, 0.0, egui::Color32::from_black_alpha(120));
+
+    let max = perf_stats.frame_times.iter().copied().fold(1.0 / 30.0, f32::max);
+    let height_for = |seconds: f32| rect.bottom() - seconds / max * rect.height();
+    let step = rect.width() / (FRAME_HISTORY - 1) as f32;
+    let points = perf_stats.frame_times.iter()
+        .enumerate()
+        .map(|(index, seconds)| egui::pos2(rect.left() + index as f32 * step, height_for(*seconds)))
+        .collect::<Vec<egui::Pos2>>();
+
+    let target = height_for(1.0 / 60.0);
+    painter.hline(rect.x_range(), target, egui::Stroke::new(1.0, egui::Color32::DARK_GRAY));
+    painter.add(egui::Shape::line(points, egui::Stroke::new(1.0, egui::Color32::LIGHT_GREEN)));
+}
+
+// Freezing pauses virtual time, so everything keeps running with a zero delta.
+// A step unpauses it for the next frame only
+pub fn apply_debug_time(
+    mut time: ResMut<Time<Virtual>>,
+    mut debug_tools: ResMut<DebugTools>,
+) {
+    if time.relative_speed() != debug_tools.time_scale {
+        time.set_relative_speed(debug_tools.time_scale);
+    }
+    if debug_tools.step_requested {
+        debug_tools.step_requested = false;
+        time.unpause();
+    } else if debug_tools.frozen {
+        time.pause();
+    } else {
+        time.unpause();
+    }
+}
+
+pub fn reset_debug_tools(
+    mut time: ResMut<Time<Virtual>>,
+    mut debug_tools: ResMut<DebugTools>,
+) {
+    *debug_tools = DebugTools::default();
+    time.set_relative_speed(1.0);
+    time.unpause();
+}
+
+// Clicks go to spawning instead of popping while this is on
+pub fn spawning_bubbles_at_cursor(
+    debug_tools: Option<Res<DebugTools>>,
+) -> bool {
+    debug_tools.is_some_and(|debug_tools| debug_tools.spawn_on_click)
+}
+
+pub fn spawn_bubble_at_cursor(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mesh_cache: Res<MeshCache>,
+    actions: Res<ActionState>,
+    debug_tools: Res<DebugTools>,
+    mut tainted: ResMut<RunTainted>,
+    pointer_capture: Res<PointerCapture>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+) {
+    if !debug_tools.spawn_on_click || !actions.just_pressed(InputAction::Pop) {
+        return;
+    }
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let Ok((camera, camera_transform)) = camera_query.get_single() else {
+        return;
+    };
+    let Some(window_position) = window.cursor_position() else {
+        return;
+    };
+    if pointer_capture.blocks_mouse(window_position) {
+        return;
+    }
+    let Some(position) = window_to_world_2d(camera, camera_transform, window_position) else {
+        return;
+    };
+
+    commands.spawn(BubbleBundle::from_type(
+        &mesh_cache,
+        &mut materials,
+        debug_tools.spawn_type,
+        position,
+        Vec2::new(0.0, 100.0),
+    ));
+    tainted.0 = true;
+}
+
+pub fn toggle_debug_on_on_input(
     mut debug_state: ResMut<NextState<DebugState>>,
-    input: Res<ButtonInput<KeyCode>>,
+    actions: Res<ActionState>,
 ) {
-    if input.just_pressed(KeyCode::Backslash) {
+    if actions.just_pressed(InputAction::ToggleDebug) {
         debug_state.set(DebugState::Debug);
     }
 }
 
-pub fn toggle_debug_off_on_backslash(
+pub fn toggle_debug_off_on_input(
     mut debug_state: ResMut<NextState<DebugState>>,
-    input: Res<ButtonInput<KeyCode>>,
+    actions: Res<ActionState>,
 ) {
-    if input.just_pressed(KeyCode::Backslash) {
+    if actions.just_pressed(InputAction::ToggleDebug) {
         debug_state.set(DebugState::NoDebug);
     }
 }
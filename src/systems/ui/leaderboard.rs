@@ -0,0 +1,85 @@
+use std::cmp::Reverse;
+
+use bevy::prelude::*;
+
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{
+    game_states::GameState,
+    persistence,
+    resources::{leaderboard::*, replay::{Replay, ReplayPlayback}, run::{GameMode, RunSeed}},
+    util,
+};
+
+pub fn ui_leaderboard(
+    mut contexts: EguiContexts,
+    mut commands: Commands,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    mut view: ResMut<LeaderboardView>,
+    mut mode: ResMut<GameMode>,
+    mut run_seed: ResMut<RunSeed>,
+    leaderboard: Res<Leaderboard>,
+) {
+    egui::Window::new("Leaderboard")
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .collapsible(false)
+        .resizable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.horizontal(|ui| {
+                for candidate in GameMode::all() {
+                    ui.selectable_value(&mut view.mode, candidate, candidate.name());
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Sort by");
+                for sort in LeaderboardSort::all() {
+                    ui.selectable_value(&mut view.sort, sort, sort.name());
+                }
+            });
+            ui.separator();
+
+            // ranks stay by score whatever the rows are sorted by
+            let mut entries = leaderboard.entries_for(view.mode).enumerate().collect::<Vec<_>>();
+            match view.sort {
+                LeaderboardSort::Score => {}
+                LeaderboardSort::Chain => entries.sort_by_key(|(_, entry)| Reverse(entry.longest_chain)),
+                LeaderboardSort::Date => entries.sort_by_key(|(_, entry)| Reverse(entry.date)),
+            }
+            if entries.is_empty() {
+                ui.label("No runs yet");
+            }
+            egui::Grid::new("leaderboard").striped(true).spacing([16.0, 6.0]).show(ui, |ui| {
+                for header in ["#", "Name", "Score", "Chain", "Date", "Seed", ""] {
+                    ui.strong(header);
+                }
+                ui.end_row();
+                for (rank, entry) in entries {
+                    ui.label((rank + 1).to_string());
+                    ui.label(&entry.name);
+                    ui.label(entry.score.to_string());
+                    ui.label(entry.longest_chain.to_string());
+                    ui.label(util::format_date(entry.date));
+                    ui.label(entry.seed.to_string());
+                    let watch = ui.add_enabled(entry.replay.is_some(), egui::Button::new("Watch"));
+                    if watch.clicked() {
+                        if let Some(replay) = entry.replay.as_deref().and_then(persistence::load::<Replay>) {
+                            *mode = replay.mode;
+                            run_seed.next = Some(replay.seed);
+                            commands.insert_resource(ReplayPlayback {
+                                replay,
+                                frame: 0,
+                                exit_when_done: false,
+                            });
+                            next_game_state.set(GameState::InGame);
+                        }
+                    }
+                    ui.end_row();
+                }
+            });
+
+            ui.separator();
+            if ui.button("Back").clicked() {
+                next_game_state.set(GameState::MainMenu);
+            }
+        });
+}
@@ -1,16 +1,22 @@
 use bevy::prelude::*;
 
-use crate::{components::ui::MainMenuRoot, game_states::GameState, util};
+use crate::{components::ui::MainMenuRoot, game_states::GameState, persistence, resources::{benchmark::{Benchmark, BENCHMARK_SEED, DEFAULT_BENCHMARK_TICKS}, run::{GameMode, RunSeed}, save::{ResumeRun, SavedRun}}, util};
 
 #[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum MainMenuAction {
+    Continue,
     Play,
+    Mode,
     Options,
+    Achievements,
+    Leaderboard,
+    Benchmark,
     Quit,
 }
 
 pub fn draw_main_menu(
     mut commands: Commands,
+    mode: Res<GameMode>,
 ) {
     let container = commands.spawn((
         Node {
@@ -47,15 +53,44 @@ pub fn draw_main_menu(
         ..default()
     }).id();
 
+    let mut buttons = vec![];
+    if persistence::exists(SavedRun::SAVE_KEY) {
+        let continue_button = util::spawn_button_with_text(
+            &mut commands, "Continue".into(), Val::Px(200.0), Val::Px(50.0), 25.0);
+        commands.entity(continue_button).insert(MainMenuAction::Continue);
+        buttons.push(continue_button);
+    }
+
     let play_button = util::spawn_button_with_text(
         &mut commands, "Play".into(), Val::Px(200.0), Val::Px(50.0), 25.0);
     commands.entity(play_button).insert(MainMenuAction::Play);
 
+    let mode_button = util::spawn_button_with_text(
+        &mut commands, mode_button_text(*mode), Val::Px(200.0), Val::Px(50.0), 25.0);
+    commands.entity(mode_button).insert(MainMenuAction::Mode);
+
+    let options_button = util::spawn_button_with_text(
+        &mut commands, "Options".into(), Val::Px(200.0), Val::Px(50.0), 25.0);
+    commands.entity(options_button).insert(MainMenuAction::Options);
+
+    let achievements_button = util::spawn_button_with_text(
+        &mut commands, "Achievements".into(), Val::Px(200.0), Val::Px(50.0), 25.0);
+    commands.entity(achievements_button).insert(MainMenuAction::Achievements);
+
+    let leaderboard_button = util::spawn_button_with_text(
+        &mut commands, "Leaderboard".into(), Val::Px(200.0), Val::Px(50.0), 25.0);
+    commands.entity(leaderboard_button).insert(MainMenuAction::Leaderboard);
+
+    let benchmark_button = util::spawn_button_with_text(
+        &mut commands, "Benchmark".into(), Val::Px(200.0), Val::Px(50.0), 25.0);
+    commands.entity(benchmark_button).insert(MainMenuAction::Benchmark);
+
     let quit_button = util::spawn_button_with_text(
         &mut commands, "Quit".into(), Val::Px(200.0), Val::Px(50.0), 25.0);
     commands.entity(quit_button).insert(MainMenuAction::Quit);
     
-    commands.entity(buttons_container).add_children(&[play_button, quit_button]);
+    buttons.extend([play_button, mode_button, options_button, achievements_button, leaderboard_button, benchmark_button, quit_button]);
+    commands.entity(buttons_container).add_children(&buttons);
 
     commands.entity(container).add_children(&[main_text, buttons_container]);
 }
@@ -69,7 +104,14 @@ pub fn cleanup_main_menu(
     }
 }
 
+fn mode_button_text(mode: GameMode) -> String {
+    format!("Mode: {}", mode.name())
+}
+
 pub fn button_system(
+    mut commands: Commands,
+    mut mode: ResMut<GameMode>,
+    mut run_seed: ResMut<RunSeed>,
     mut next_game_state: ResMut<NextState<GameState>>,
     mut app_exit: EventWriter<AppExit>,
     mut interaction_query: Query<
@@ -85,7 +127,7 @@ pub fn button_system(
         match *interaction {
             Interaction::Pressed => {
                 border_color.0 = Color::srgb(0.3, 0.5, 0.8);
-                perform_action(&mut next_game_state, &mut app_exit, *action);
+                perform_action(&mut commands, &mut mode, &mut run_seed, &mut next_game_state, &mut app_exit, *action);
             }
             Interaction::Hovered => {
                 border_color.0 = Color::srgb(0.5, 0.7, 1.0);
@@ -98,17 +140,66 @@ pub fn button_system(
 }
 
 fn perform_action(
+    commands: &mut Commands,
+    mode: &mut GameMode,
+    run_seed: &mut RunSeed,
     next_game_state: &mut NextState<GameState>,
     exit: &mut EventWriter<AppExit>,
     action: MainMenuAction,
 ) {
     match action {
+        MainMenuAction::Continue => {
+            let Some(saved_run) = persistence::load::<SavedRun>(SavedRun::SAVE_KEY) else {
+                return;
+            };
+            *mode = saved_run.mode;
+            commands.insert_resource(ResumeRun(saved_run));
+            next_game_state.set(GameState::InGame);
+        }
         MainMenuAction::Play => {
             next_game_state.set(GameState::InGame);
         }
+        MainMenuAction::Mode => {
+            let modes = GameMode::all();
+            let index = modes.iter().position(|candidate| candidate == &*mode).unwrap_or_default();
+            *mode = modes[(index + 1) % modes.len()];
+        }
+        MainMenuAction::Options => {
+            next_game_state.set(GameState::Options);
+        }
+        MainMenuAction::Achievements => {
+            next_game_state.set(GameState::Achievements);
+        }
+        MainMenuAction::Leaderboard => {
+            next_game_state.set(GameState::Leaderboard);
+        }
+        MainMenuAction::Benchmark => {
+            commands.insert_resource(Benchmark::new(DEFAULT_BENCHMARK_TICKS, false));
+            run_seed.next = Some(BENCHMARK_SEED);
+            next_game_state.set(GameState::InGame);
+        }
         MainMenuAction::Quit => {
             exit.send(AppExit::Success);
         }
-        _ => {}
+    }
+}
+
+pub fn update_mode_button(
+    mode: Res<GameMode>,
+    button_query: Query<(&MainMenuAction, &Children)>,
+    mut text_query: Query<&mut Text>,
+) {
+    if !mode.is_changed() {
+        return;
+    }
+    for (action, children) in &button_query {
+        if *action != MainMenuAction::Mode {
+            continue;
+        }
+        for child in children {
+            if let Ok(mut text) = text_query.get_mut(*child) {
+                text.0 = mode_button_text(*mode);
+            }
+        }
     }
 }
@@ -0,0 +1,112 @@
+use bevy::prelude::*;
+
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{game_states::GameState, resources::{audio::{AudioCategory, AudioVolumes, MusicalScale, PitchSettings, StereoSettings}, input::{ActionMap, InputAction, RebindState}}};
+
+pub fn ui_options_menu(
+    mut contexts: EguiContexts,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    mut action_map: ResMut<ActionMap>,
+    mut rebind_state: ResMut<RebindState>,
+    mut volumes: ResMut<AudioVolumes>,
+    mut stereo_settings: ResMut<StereoSettings>,
+    mut pitch_settings: ResMut<PitchSettings>,
+) {
+    egui::Window::new("Options")
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .collapsible(false)
+        .resizable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.heading("Audio");
+            // editing a copy keeps change detection (and saving) to actual edits
+            let mut edited = volumes.clone();
+            egui::Grid::new("volumes").show(ui, |ui| {
+                ui.label("Master");
+                ui.add(egui::Slider::new(&mut edited.master, 0.0..=1.0));
+                ui.end_row();
+                for category in AudioCategory::all() {
+                    ui.label(category.name());
+                    ui.add(egui::Slider::new(edited.category_mut(category), 0.0..=1.0));
+                    ui.end_row();
+                }
+            });
+            volumes.set_if_neq(edited);
+
+            let mut mono = stereo_settings.mono;
+            ui.checkbox(&mut mono, "Mono output");
+            if mono != stereo_settings.mono {
+                stereo_settings.mono = mono;
+            }
+
+            // chains play their pops as notes climbing this scale
+            let mut edited = pitch_settings.clone();
+            ui.horizontal(|ui| {
+                ui.label("Pop notes");
+                egui::ComboBox::from_id_salt("pitch_key")
+                    .selected_text(PitchSettings::key_name(edited.key))
+                    .show_ui(ui, |ui| {
+                        for key in 0..12 {
+                            ui.selectable_value(&mut edited.key, key, PitchSettings::key_name(key));
+                        }
+                    });
+                egui::ComboBox::from_id_salt("pitch_scale")
+                    .selected_text(edited.scale.name())
+                    .show_ui(ui, |ui| {
+                        for scale in MusicalScale::all() {
+                            ui.selectable_value(&mut edited.scale, scale, scale.name());
+                        }
+                    });
+            });
+            pitch_settings.set_if_neq(edited);
+
+            ui.separator();
+            ui.heading("Controls");
+            ui.label("Click a binding to change it, right click to remove it.");
+
+            // the bindings list is longer than a small window is tall
+            egui::ScrollArea::vertical().max_height(360.0).show(ui, |ui| {
+                egui::Grid::new("bindings").striped(true).show(ui, |ui| {
+                    for action in InputAction::all() {
+                        ui.label(action.name());
+                        ui.horizontal(|ui| {
+                            let bindings = action_map.bindings_for(action).to_vec();
+                            // the slot one past the end is the "+" button for adding a binding
+                            for slot in 0..=bindings.len() {
+                                let text = if rebind_state.waiting_for == Some((action, slot)) {
+                                    "Press a button...".to_string()
+                                } else if let Some(binding) = bindings.get(slot) {
+                                    binding.name()
+                                } else {
+                                    "+".to_string()
+                                };
+                                let response = ui.button(text);
+                                if response.clicked() {
+                                    rebind_state.waiting_for = Some((action, slot));
+                                }
+                                if response.secondary_clicked() && slot < bindings.len() {
+                                    action_map.unbind(action, slot);
+                                }
+                            }
+                        });
+                        ui.end_row();
+                    }
+                });
+            });
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui.button("Reset Controls").clicked() {
+                    *action_map = ActionMap::default();
+                    rebind_state.waiting_for = None;
+                }
+                if rebind_state.waiting_for.is_some() && ui.button("Cancel").clicked() {
+                    rebind_state.waiting_for = None;
+                }
+                if ui.button("Back").clicked() {
+                    rebind_state.waiting_for = None;
+                    next_game_state.set(GameState::MainMenu);
+                }
+            });
+        });
+}
@@ -1,10 +1,11 @@
 use bevy::prelude::*;
 
-use crate::{components::ui::PauseMenuRoot, game_states::{GameState, PausedState}, util};
+use crate::{components::ui::PauseMenuRoot, game_states::{GameState, PausedState}, resources::input::{ActionState, InputAction}, util};
 
 #[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum PauseMenuAction {
     Continue,
+    EndRun,
     ReturnToMainMenu,
 }
 
@@ -50,11 +51,15 @@ pub fn draw_pause_menu(
         &mut commands, "Continue".into(), Val::Px(200.0), Val::Px(50.0), 25.0);
     commands.entity(continue_button).insert(PauseMenuAction::Continue);
 
+    let end_run_button = util::spawn_button_with_text(
+        &mut commands, "End Run".into(), Val::Px(200.0), Val::Px(50.0), 25.0);
+    commands.entity(end_run_button).insert(PauseMenuAction::EndRun);
+
     let main_menu_button = util::spawn_button_with_text(
         &mut commands, "Main Menu".into(), Val::Px(200.0), Val::Px(50.0), 25.0);
     commands.entity(main_menu_button).insert(PauseMenuAction::ReturnToMainMenu);
     
-    commands.entity(buttons_container).add_children(&[continue_button, main_menu_button]);
+    commands.entity(buttons_container).add_children(&[continue_button, end_run_button, main_menu_button]);
 
     commands.entity(container).add_children(&[main_text, buttons_container]);
 }
@@ -105,26 +110,30 @@ fn perform_action(
         PauseMenuAction::Continue => {
             next_paused_state.set(PausedState::Unpaused);
         }
+        // the saved run is kept for Continue unless the run is ended outright
+        PauseMenuAction::EndRun => {
+            next_game_state.set(GameState::Summary);
+        }
         PauseMenuAction::ReturnToMainMenu => {
             next_game_state.set(GameState::MainMenu);
         }
     }
 }
 
-pub fn pause_game_on_esc(
+pub fn pause_game_on_input(
     mut paused_state: ResMut<NextState<PausedState>>,
-    keys: Res<ButtonInput<KeyCode>>,
+    actions: Res<ActionState>,
 ) {
-    if keys.just_pressed(KeyCode::Escape) {
+    if actions.just_pressed(InputAction::Pause) {
         paused_state.set(PausedState::Paused);
     }
 }
 
-pub fn unpause_game_on_esc(
+pub fn unpause_game_on_input(
     mut paused_state: ResMut<NextState<PausedState>>,
-    keys: Res<ButtonInput<KeyCode>>,
+    actions: Res<ActionState>,
 ) {
-    if keys.just_pressed(KeyCode::Escape) {
+    if actions.just_pressed(InputAction::Pause) {
         paused_state.set(PausedState::Unpaused);
     }
 }
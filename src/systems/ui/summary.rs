@@ -0,0 +1,138 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{
+    components::bubbles::BubbleType,
+    game_states::GameState,
+    resources::{leaderboard::{Leaderboard, PendingLeaderboardEntry}, run::{GameMode, RunSeed}, stats::{GameStats, RunStats}},
+    systems::{leaderboard::submit_leaderboard_entry, ui::upgrades_menu::UpgradesMenuAction},
+};
+
+#[derive(SystemParam)]
+pub struct RunSummary<'w> {
+    game_stats: Res<'w, GameStats>,
+    mode: Res<'w, GameMode>,
+    run_seed: Res<'w, RunSeed>,
+    run_stats: Res<'w, RunStats>,
+}
+
+pub fn ui_summary(
+    mut contexts: EguiContexts,
+    mut commands: Commands,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    mut leaderboard: ResMut<Leaderboard>,
+    pending: Option<ResMut<PendingLeaderboardEntry>>,
+    summary: RunSummary,
+) {
+    let RunSummary { game_stats, mode, run_seed, run_stats } = summary;
+    // headless runs have nothing to draw into
+    let Some(ctx) = contexts.try_ctx_mut() else {
+        return;
+    };
+    egui::Window::new("Run Over")
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .collapsible(false)
+        .resizable(false)
+        .show(ctx, |ui| {
+            ui.heading(format!("Score: {}", game_stats.score));
+            if let Some(mut pending) = pending {
+                ui.label("New high score!");
+                ui.horizontal(|ui| {
+                    ui.label("Name");
+                    ui.text_edit_singleline(&mut pending.entry.name);
+                    if ui.button("Save").clicked() {
+                        submit_leaderboard_entry(&mut leaderboard, &pending);
+                        commands.remove_resource::<PendingLeaderboardEntry>();
+                    }
+                });
+            }
+            let seconds = run_stats.time_alive as u32;
+            egui::Grid::new("summary_stats").striped(true).show(ui, |ui| {
+                let rows = [
+                    ("Mode", mode.name().to_string()),
+                    ("Seed", run_seed.current.to_string()),
+                    ("Time alive", format!("{}:{:02}", seconds / 60, seconds % 60)),
+                    ("Bubbles popped", run_stats.total_popped().to_string()),
+                    ("Your pops", run_stats.user_pops.to_string()),
+                    ("Pops from effects", run_stats.effect_pops.to_string()),
+                    ("Longest chain", run_stats.longest_chain.to_string()),
+                    ("Peak pops per second", run_stats.peak_pops_per_second.to_string()),
+                    ("Upgrades bought", run_stats.total_upgrades_bought().to_string()),
+                ];
+                for (name, value) in rows {
+                    ui.label(name);
+                    ui.label(value);
+                    ui.end_row();
+                }
+            });
+
+            ui.separator();
+            ui.label("Popped by type");
+            draw_bar_chart(ui, "summary_popped", BubbleType::all()
+                .map(|bubble_type| (bubble_type.name(), *run_stats.popped.get(&bubble_type).unwrap_or(&0) as i64))
+                .to_vec());
+
+            ui.label("Score by effect");
+            draw_bar_chart(ui, "summary_score", BubbleType::all()
+                .map(|bubble_type| (bubble_type.name(), *run_stats.score_by_source.get(&bubble_type).unwrap_or(&0) as i64))
+                .to_vec());
+
+            let upgrades = UpgradesMenuAction::all()
+                .into_iter()
+                .filter_map(|action| run_stats.upgrades_bought.get(&action).map(|count| (action.name(), *count as i64)))
+                .collect::<Vec<_>>();
+            if !upgrades.is_empty() {
+                ui.label("Upgrades");
+                draw_bar_chart(ui, "summary_upgrades", upgrades);
+            }
+
+            ui.label("Pops per second");
+            draw_pops_graph(ui, &run_stats.pops_per_second);
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui.button("Play Again").clicked() {
+                    next_game_state.set(GameState::InGame);
+                }
+                if ui.button("Main Menu").clicked() {
+                    next_game_state.set(GameState::MainMenu);
+                }
+            });
+        });
+}
+
+// One bar per row, scaled so the largest value fills the width
+fn draw_bar_chart(ui: &mut egui::Ui, id: &str, rows: Vec<(&str, i64)>) {
+    let max = rows.iter().map(|(_, value)| *value).max().unwrap_or(0).max(1);
+    egui::Grid::new(id).show(ui, |ui| {
+        for (name, value) in rows {
+            ui.label(name);
+            let (response, painter) = ui.allocate_painter(egui::vec2(160.0, 14.0), egui::Sense::hover());
+            let rect = response.rect;
+            painter.rect_filled(rect, 0.0, egui::Color32::from_black_alpha(120));
+            let width = rect.width() * value.max(0) as f32 / max as f32;
+            let bar = egui::Rect::from_min_size(rect.min, egui::vec2(width, rect.height()));
+            painter.rect_filled(bar, 0.0, egui::Color32::LIGHT_BLUE);
+            ui.label(value.to_string());
+            ui.end_row();
+        }
+    });
+}
+
+fn draw_pops_graph(ui: &mut egui::Ui, pops_per_second: &[u32]) {
+    let (response, painter) = ui.allocate_painter(egui::vec2(320.0, 80.0), egui::Sense::hover());
+    let rect = response.rect;
+    painter.rect_filled(rect, 0.0, egui::Color32::from_black_alpha(120));
+    if pops_per_second.len() < 2 {
+        return;
+    }
+
+    let max = pops_per_second.iter().copied().max().unwrap_or(0).max(1) as f32;
+    let step = rect.width() / (pops_per_second.len() - 1) as f32;
+    let points = pops_per_second.iter()
+        .enumerate()
+        .map(|(index, pops)| egui::pos2(rect.left() + index as f32 * step, rect.bottom() - *pops as f32 / max * rect.height()))
+        .collect::<Vec<egui::Pos2>>();
+    painter.add(egui::Shape::line(points, egui::Stroke::new(1.0, egui::Color32::LIGHT_GREEN)));
+}
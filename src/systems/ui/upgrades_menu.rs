@@ -1,13 +1,62 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, window::PrimaryWindow};
+use serde::{Deserialize, Serialize};
 
-use crate::{components::{bubbles::BubbleType, ui::{Activatable, UpgradesMenuRoot}}, resources::{bubbles::{BubbleChances, BubbleSpawnTimer}, stats::GameStats, ui::{UpgradeChangedEvent, UpgradesMenuInfo}}, util};
+use crate::{components::{bubbles::BubbleType, ui::{Activatable, UpgradesMenuButtons, UpgradesMenuRoot}}, resources::{bubbles::{BubbleChances, BubbleSpawnTimer}, input::{ActionMap, ActionState, InputAction, InputBinding}, pop_tools::{PopTool, PopTools}, stats::GameStats, ui::{UpgradeChangedEvent, UpgradesMenuInfo}}, systems::pop_tools::select_pop_tool, util};
 
-#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum UpgradesMenuAction {
     IncreaseRate,
     IncreaseChance(BubbleType),
+    UnlockTool(PopTool),
+}
+
+impl UpgradesMenuAction {
+    // Every upgrade in menu order, which is also the order of the hotkey slots
+    pub fn all() -> [UpgradesMenuAction; 8] {
+        [
+            UpgradesMenuAction::IncreaseRate,
+            UpgradesMenuAction::IncreaseChance(BubbleType::Beam),
+            UpgradesMenuAction::IncreaseChance(BubbleType::ScatterShot),
+            UpgradesMenuAction::IncreaseChance(BubbleType::BlackHole),
+            UpgradesMenuAction::IncreaseChance(BubbleType::Mega),
+            UpgradesMenuAction::UnlockTool(PopTool::Sweep),
+            UpgradesMenuAction::UnlockTool(PopTool::Charge),
+            UpgradesMenuAction::UnlockTool(PopTool::Area),
+        ]
+    }
+
+    // Tools only need buying once, everything else stacks forever
+    pub fn max_purchases(&self) -> Option<u32> {
+        match self {
+            UpgradesMenuAction::UnlockTool(_) => Some(1),
+            _ => None,
+        }
+    }
+
+    pub fn slot(&self) -> u8 {
+        Self::all().iter().position(|action| action == self).unwrap_or(0) as u8 + 1
+    }
+
+    pub fn from_slot(slot: u8) -> Option<UpgradesMenuAction> {
+        Self::all().get((slot as usize).checked_sub(1)?).copied()
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            UpgradesMenuAction::IncreaseRate => "Increase Rate",
+            UpgradesMenuAction::IncreaseChance(BubbleType::Beam) => "Beam Up",
+            UpgradesMenuAction::IncreaseChance(BubbleType::ScatterShot) => "Scatter Shot Up",
+            UpgradesMenuAction::IncreaseChance(BubbleType::BlackHole) => "Black Hole Up",
+            UpgradesMenuAction::IncreaseChance(BubbleType::Mega) => "Mega Up",
+            UpgradesMenuAction::IncreaseChance(BubbleType::Normal) => "Normal Up",
+            UpgradesMenuAction::UnlockTool(tool) => tool.name(),
+        }
+    }
 }
 
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UpgradesMenuAutoBuy(pub UpgradesMenuAction);
+
 pub fn draw_upgrades_menu(
     mut commands: Commands,
 ) {
@@ -39,39 +88,87 @@ pub fn draw_upgrades_menu(
             },
             ..default()
         },
+        UpgradesMenuButtons,
     )).id();
 
+    for action in UpgradesMenuAction::all() {
+        let row = commands.spawn(Node {
+            display: Display::Flex,
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(5.0),
+            ..default()
+        }).id();
 
-    let rate_button = util::spawn_button_with_text(
-        &mut commands, "Increase Rate".into(), Val::Px(200.0), Val::Px(35.0), 12.0);
-    commands.entity(rate_button).insert(UpgradesMenuAction::IncreaseRate);
-    commands.entity(rate_button).insert(Activatable::new(true));
+        let button = util::spawn_button_with_text(
+            &mut commands, action.name().into(), Val::Px(200.0), Val::Px(35.0), 12.0);
+        commands.entity(button).insert(action);
+        commands.entity(button).insert(Activatable::new(true));
+        commands.entity(row).add_children(&[button]);
 
-    let beam_button = util::spawn_button_with_text(
-        &mut commands, "Beam Up".into(), Val::Px(200.0), Val::Px(35.0), 12.0);
-    commands.entity(beam_button).insert(UpgradesMenuAction::IncreaseChance(BubbleType::Beam));
-    commands.entity(beam_button).insert(Activatable::new(true));
+        if action.max_purchases().is_none() {
+            let auto_buy_button = util::spawn_button_with_text(
+                &mut commands, "A".into(), Val::Px(35.0), Val::Px(35.0), 12.0);
+            commands.entity(auto_buy_button).insert(UpgradesMenuAutoBuy(action));
+            commands.entity(row).add_children(&[auto_buy_button]);
+        }
 
-    let scatter_shot_button = util::spawn_button_with_text(
-        &mut commands, "Scatter Shot Up".into(), Val::Px(200.0), Val::Px(35.0), 12.0);
-    commands.entity(scatter_shot_button).insert(UpgradesMenuAction::IncreaseChance(BubbleType::ScatterShot));
-    commands.entity(scatter_shot_button).insert(Activatable::new(true));
+        commands.entity(buttons_container).add_children(&[row]);
+    }
 
-    let black_hole_button = util::spawn_button_with_text(
-        &mut commands, "Black Hole Up".into(), Val::Px(200.0), Val::Px(35.0), 12.0);
-    commands.entity(black_hole_button).insert(UpgradesMenuAction::IncreaseChance(BubbleType::BlackHole));
-    commands.entity(black_hole_button).insert(Activatable::new(true));
+    commands.entity(container).add_children(&[buttons_container]);
+}
 
-    let mega_button = util::spawn_button_with_text(
-        &mut commands, "Mega Up".into(), Val::Px(200.0), Val::Px(35.0), 12.0);
-    commands.entity(mega_button).insert(UpgradesMenuAction::IncreaseChance(BubbleType::Mega));
-    commands.entity(mega_button).insert(Activatable::new(true));
-    
-    commands.entity(buttons_container).add_children(&[
-        rate_button, beam_button, scatter_shot_button, black_hole_button, mega_button
-    ]);
+// Narrow or portrait windows (phones, mostly) don't have room for a column down
+// the side, so the buttons move into a wrapping row along the bottom instead and
+// get tall enough to hit comfortably with a finger
+pub fn adapt_upgrades_menu_layout(
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    changed_window_query: Query<(), Changed<Window>>,
+    added_menu_query: Query<(), Added<UpgradesMenuRoot>>,
+    mut root_query: Query<&mut Node, (With<UpgradesMenuRoot>, Without<UpgradesMenuButtons>, Without<Button>)>,
+    mut buttons_container_query: Query<&mut Node, (With<UpgradesMenuButtons>, Without<Button>)>,
+    mut button_query: Query<(&mut Node, Has<UpgradesMenuAction>), (With<Button>, Or<(With<UpgradesMenuAction>, With<UpgradesMenuAutoBuy>)>)>,
+) {
+    if changed_window_query.is_empty() && added_menu_query.is_empty() {
+        return;
+    }
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let compact = window.width() < window.height() || window.width() < 700.0;
 
-    commands.entity(container).add_children(&[buttons_container]);
+    // the root justifies to the end either way, so flipping its direction is
+    // enough to move the buttons from the right edge to the bottom
+    for mut node in &mut root_query {
+        node.flex_direction = if compact { FlexDirection::Column } else { FlexDirection::Row };
+    }
+
+    for mut node in &mut buttons_container_query {
+        if compact {
+            node.flex_direction = FlexDirection::Row;
+            node.flex_wrap = FlexWrap::Wrap;
+            node.column_gap = Val::Px(10.0);
+            node.row_gap = Val::Px(10.0);
+            node.padding = UiRect::all(Val::Px(10.0));
+        } else {
+            node.flex_direction = FlexDirection::Column;
+            node.flex_wrap = FlexWrap::NoWrap;
+            node.column_gap = Val::Px(0.0);
+            node.row_gap = Val::Px(20.0);
+            node.padding = UiRect::all(Val::Px(20.0));
+        }
+    }
+
+    let height = if compact { 44.0 } else { 35.0 };
+    for (mut button_node, is_upgrade) in &mut button_query {
+        button_node.height = Val::Px(height);
+        button_node.width = match (is_upgrade, compact) {
+            (true, true) => Val::Px(150.0),
+            (true, false) => Val::Px(200.0),
+            (false, _) => Val::Px(height),
+        };
+    }
 }
 
 pub fn cleanup_upgrades_menu(
@@ -84,6 +181,8 @@ pub fn cleanup_upgrades_menu(
 }
 
 pub fn button_interactions(
+    actions: Res<ActionState>,
+    mut pop_tools: ResMut<PopTools>,
     mut spawn_timer: ResMut<BubbleSpawnTimer>,
     mut spawn_chances: ResMut<BubbleChances>,
     mut upgrades: ResMut<UpgradesMenuInfo>,
@@ -107,14 +206,121 @@ pub fn button_interactions(
         match *interaction {
             Interaction::Pressed => {
                 border_color.0 = Color::srgb(0.3, 0.5, 0.8);
-                let old_cost = *upgrades.costs.get(action).unwrap_or(&0);
-                if stats.score < old_cost as i32 {
-                    continue;
+                if let UpgradesMenuAction::UnlockTool(tool) = *action {
+                    if upgrades.owns_tool(tool) {
+                        toggle_pop_tool(&mut pop_tools, &mut unlock_events, tool);
+                        continue;
+                    }
                 }
-                upgrades.costs.insert(*action, (old_cost as f32 * 1.1) as u32);
-                stats.score -= old_cost as i32;
-                perform_action(&mut spawn_timer, &mut spawn_chances, *action);
-                unlock_events.send(UpgradeChangedEvent { action: *action });
+                let amount = if actions.pressed(InputAction::BuyMaxModifier) { u32::MAX } else { 1 };
+                purchase_upgrade(
+                    &mut spawn_timer,
+                    &mut spawn_chances,
+                    &mut upgrades,
+                    &mut stats,
+                    &mut unlock_events,
+                    *action,
+                    amount,
+                );
+            }
+            Interaction::Hovered => {
+                border_color.0 = Color::srgb(0.5, 0.7, 1.0);
+            }
+            Interaction::None => {
+                border_color.0 = Color::WHITE;
+            }
+        }
+    }
+}
+
+pub fn buy_upgrades_on_input(
+    actions: Res<ActionState>,
+    mut pop_tools: ResMut<PopTools>,
+    mut spawn_timer: ResMut<BubbleSpawnTimer>,
+    mut spawn_chances: ResMut<BubbleChances>,
+    mut upgrades: ResMut<UpgradesMenuInfo>,
+    mut stats: ResMut<GameStats>,
+    mut unlock_events: EventWriter<UpgradeChangedEvent>,
+) {
+    for action in UpgradesMenuAction::all() {
+        if !actions.just_pressed(InputAction::BuyUpgrade(action.slot())) {
+            continue;
+        }
+
+        if let UpgradesMenuAction::UnlockTool(tool) = action {
+            if upgrades.owns_tool(tool) {
+                toggle_pop_tool(&mut pop_tools, &mut unlock_events, tool);
+                continue;
+            }
+        }
+
+        if actions.pressed(InputAction::AutoBuyModifier) {
+            if action.max_purchases().is_some() {
+                continue;
+            }
+            upgrades.toggle_auto_buy(action);
+            // the event makes the menu pick up the new auto-buy state
+            unlock_events.send(UpgradeChangedEvent { action });
+            continue;
+        }
+
+        if !upgrades.is_unlocked(action) {
+            continue;
+        }
+        let amount = if actions.pressed(InputAction::BuyMaxModifier) { u32::MAX } else { 1 };
+        purchase_upgrade(
+            &mut spawn_timer,
+            &mut spawn_chances,
+            &mut upgrades,
+            &mut stats,
+            &mut unlock_events,
+            action,
+            amount,
+        );
+    }
+}
+
+pub fn auto_buy_upgrades(
+    mut spawn_timer: ResMut<BubbleSpawnTimer>,
+    mut spawn_chances: ResMut<BubbleChances>,
+    mut upgrades: ResMut<UpgradesMenuInfo>,
+    mut stats: ResMut<GameStats>,
+    mut unlock_events: EventWriter<UpgradeChangedEvent>,
+) {
+    for action in UpgradesMenuAction::all() {
+        if !upgrades.is_auto_buying(action) || !upgrades.is_unlocked(action) {
+            continue;
+        }
+        purchase_upgrade(
+            &mut spawn_timer,
+            &mut spawn_chances,
+            &mut upgrades,
+            &mut stats,
+            &mut unlock_events,
+            action,
+            1,
+        );
+    }
+}
+
+pub fn auto_buy_button_interactions(
+    mut upgrades: ResMut<UpgradesMenuInfo>,
+    mut unlock_events: EventWriter<UpgradeChangedEvent>,
+    mut interaction_query: Query<
+        (
+            &UpgradesMenuAutoBuy,
+            &Interaction,
+            &mut BorderColor,
+        ),
+        (Changed<Interaction>, With<Button>)
+    >,
+) {
+    for (auto_buy, interaction, mut border_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                border_color.0 = Color::srgb(0.3, 0.5, 0.8);
+                upgrades.toggle_auto_buy(auto_buy.0);
+                unlock_events.send(UpgradeChangedEvent { action: auto_buy.0 });
             }
             Interaction::Hovered => {
                 border_color.0 = Color::srgb(0.5, 0.7, 1.0);
@@ -128,18 +334,37 @@ pub fn button_interactions(
 
 pub fn update_upgrades_menu(
     upgrades: Res<UpgradesMenuInfo>,
+    pop_tools: Res<PopTools>,
+    action_map: Res<ActionMap>,
     mut unlock_events: EventReader<UpgradeChangedEvent>,
     mut button_query: Query<(&UpgradesMenuAction, &Children, &mut Activatable, &mut BorderColor), With<Button>>,
+    mut auto_buy_query: Query<(&UpgradesMenuAutoBuy, &mut BackgroundColor), With<Button>>,
     mut text_query: Query<(&mut Text, &mut TextColor)>,
 ) {
     for event in unlock_events.read() {
+        for (auto_buy, mut background_color) in &mut auto_buy_query {
+            if event.action == auto_buy.0 {
+                background_color.0 = if upgrades.is_auto_buying(auto_buy.0) {
+                    Color::srgb(0.3, 0.7, 0.4)
+                } else {
+                    Color::srgb(0.3, 0.5, 0.8)
+                };
+            }
+        }
+
         for (action, children, mut activatable, mut border_color) in &mut button_query {
             if event.action == *action {
                 let (mut text, mut text_color) = text_query.get_mut(children[0]).unwrap();
 
-                let string = text.0.clone();
-                let text_without_score = string.split(" (").collect::<Vec<&str>>()[0];
-                text.0 = format!("{} ({})", text_without_score, upgrades.costs.get(action).unwrap_or(&0));
+                let price = match *action {
+                    UpgradesMenuAction::UnlockTool(tool) if pop_tools.selected == tool => "active".to_string(),
+                    UpgradesMenuAction::UnlockTool(tool) if upgrades.owns_tool(tool) => "owned".to_string(),
+                    _ => upgrades.costs.get(action).unwrap_or(&0).to_string(),
+                };
+                text.0 = match hotkey_label(&action_map, *action) {
+                    Some(hotkey) => format!("[{}] {} ({})", hotkey, action.name(), price),
+                    None => format!("{} ({})", action.name(), price),
+                };
 
                 activatable.active = upgrades.is_unlocked(*action);
 
@@ -155,6 +380,57 @@ pub fn update_upgrades_menu(
     }
 }
 
+// Buys up to `amount` of an upgrade, stopping early once it's unaffordable
+pub fn purchase_upgrade(
+    spawn_timer: &mut BubbleSpawnTimer,
+    spawn_chances: &mut BubbleChances,
+    upgrades: &mut UpgradesMenuInfo,
+    stats: &mut GameStats,
+    unlock_events: &mut EventWriter<UpgradeChangedEvent>,
+    action: UpgradesMenuAction,
+    amount: u32,
+) {
+    let limit = action.max_purchases()
+        .map(|max| max.saturating_sub(upgrades.purchase_count(action)))
+        .unwrap_or(u32::MAX);
+    let mut bought = 0;
+    while bought < amount.min(limit) {
+        let old_cost = *upgrades.costs.get(&action).unwrap_or(&0);
+        if stats.score < old_cost as i32 {
+            break;
+        }
+        upgrades.costs.insert(action, (old_cost as f32 * 1.1) as u32);
+        stats.score -= old_cost as i32;
+        perform_action(spawn_timer, spawn_chances, action);
+        upgrades.record_purchase(action);
+        bought += 1;
+    }
+    if bought > 0 {
+        unlock_events.send(UpgradeChangedEvent { action });
+    }
+}
+
+// Owned tool buttons switch between that tool and the plain click
+fn toggle_pop_tool(
+    pop_tools: &mut PopTools,
+    unlock_events: &mut EventWriter<UpgradeChangedEvent>,
+    tool: PopTool,
+) {
+    let tool = if pop_tools.selected == tool { PopTool::Click } else { tool };
+    select_pop_tool(pop_tools, unlock_events, tool);
+}
+
+// The first keyboard binding for the upgrade's slot, shortened to fit on the button
+fn hotkey_label(action_map: &ActionMap, action: UpgradesMenuAction) -> Option<String> {
+    action_map.bindings_for(InputAction::BuyUpgrade(action.slot()))
+        .iter()
+        .find_map(|binding| match binding {
+            InputBinding::Key(key) => Some(format!("{:?}", key)),
+            _ => None,
+        })
+        .map(|name| name.trim_start_matches("Digit").trim_start_matches("Key").to_string())
+}
+
 fn perform_action(
     spawn_timer: &mut BubbleSpawnTimer,
     spawn_chances: &mut BubbleChances,
@@ -167,6 +443,8 @@ fn perform_action(
         UpgradesMenuAction::IncreaseChance(bubble_type) => {
             spawn_chances.add_chance(bubble_type, 1.0);
         }
+        // owning a tool is tracked by its purchase count, there's nothing else to change
+        UpgradesMenuAction::UnlockTool(_) => {}
     }
 }
 
@@ -174,10 +452,8 @@ pub fn reset_upgrades(
     mut commands: Commands,
     mut unlock_events: EventWriter<UpgradeChangedEvent>,
 ) {
-    commands.init_resource::<UpgradesMenuInfo>();
-    unlock_events.send(UpgradeChangedEvent { action: UpgradesMenuAction::IncreaseRate });
-    unlock_events.send(UpgradeChangedEvent { action: UpgradesMenuAction::IncreaseChance(BubbleType::Beam) });
-    unlock_events.send(UpgradeChangedEvent { action: UpgradesMenuAction::IncreaseChance(BubbleType::ScatterShot) });
-    unlock_events.send(UpgradeChangedEvent { action: UpgradesMenuAction::IncreaseChance(BubbleType::BlackHole) });
-    unlock_events.send(UpgradeChangedEvent { action: UpgradesMenuAction::IncreaseChance(BubbleType::Mega) });
+    commands.insert_resource(UpgradesMenuInfo::default());
+    for action in UpgradesMenuAction::all() {
+        unlock_events.send(UpgradeChangedEvent { action });
+    }
 }
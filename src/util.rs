@@ -1,8 +1,11 @@
 use std::time::Duration;
 
 use bevy::{prelude::*, window::PrimaryWindow};
+use serde::{Deserialize, Serialize};
 
-#[derive(Component, Resource, Debug, Default, Clone)]
+use crate::components::camera::MainCamera;
+
+#[derive(Component, Resource, Debug, Default, Clone, Serialize, Deserialize)]
 pub struct ActionTimer {
     pub timer: Timer,
     pub amount: u64,
@@ -148,13 +151,14 @@ pub fn spawn_button_with_text(
 
 pub fn get_viewport_bounds(
     window_query: &Query<&Window, With<PrimaryWindow>>,
-    camera_query: &Query<(&Camera, &GlobalTransform)>,
+    camera_query: &Query<(&Camera, &GlobalTransform), With<MainCamera>>,
 ) -> Option<Rect> {
-    let min_bounds = Vec2::new(0.0, 0.0);
-    let max_bounds = window_query.get_single().ok()?.size();
-
     let (camera, camera_transform) = camera_query.get_single().ok()?;
 
+    let min_bounds = Vec2::new(0.0, 0.0);
+    let max_bounds = camera.logical_viewport_size()
+        .or_else(|| window_query.get_single().ok().map(|window| window.size()))?;
+
     let top_left_world_pos = camera.viewport_to_world_2d(camera_transform, min_bounds).ok()?;
     let bottom_right_world_pos = camera.viewport_to_world_2d(camera_transform, max_bounds).ok()?;
 
@@ -168,3 +172,81 @@ pub fn random_f32(rng: u64, min: f32, max: f32) -> f32 {
     let norm = rng as f32 / u64::MAX as f32;
     min + norm * (max - min)
 }
+
+// Converts a cursor position in window coordinates into the world, ignoring
+// anything that lands outside the camera's viewport (e.g. in letterbox bars)
+pub fn window_to_world_2d(
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    window_position: Vec2,
+) -> Option<Vec2> {
+    let viewport_rect = camera.logical_viewport_rect()?;
+    if !viewport_rect.contains(window_position) {
+        return None;
+    }
+    camera.viewport_to_world_2d(camera_transform, window_position - viewport_rect.min).ok()
+}
+
+pub fn world_to_window_2d(
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    world_position: Vec2,
+) -> Option<Vec2> {
+    let viewport_position = camera.world_to_viewport(camera_transform, world_position.extend(0.0)).ok()?;
+    let offset = camera.logical_viewport_rect().map(|rect| rect.min).unwrap_or(Vec2::ZERO);
+    Some(viewport_position + offset)
+}
+
+// Seconds since the unix epoch
+#[cfg(not(target_arch = "wasm32"))]
+pub fn unix_time() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn unix_time() -> u64 {
+    (web_sys::js_sys::Date::now() / 1000.0) as u64
+}
+
+// Formats a unix time as a UTC `YYYY-MM-DD HH:MM`
+pub fn format_date(unix_time: u64) -> String {
+    let days = (unix_time / 86400) as i64;
+    let seconds = unix_time % 86400;
+    // civil-from-days, see http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02} {:02}:{:02}", year, month, day, seconds / 3600, seconds % 3600 / 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_the_epoch() {
+        assert_eq!(format_date(0), "1970-01-01 00:00");
+    }
+
+    #[test]
+    fn formats_a_leap_day() {
+        assert_eq!(format_date(1709164800), "2024-02-29 00:00");
+        assert_eq!(format_date(1709213820), "2024-02-29 13:37");
+    }
+
+    #[test]
+    fn formats_across_a_century() {
+        // 2000 is a leap year, even though it's divisible by 100
+        assert_eq!(format_date(951868740), "2000-02-29 23:59");
+        assert_eq!(format_date(951868800), "2000-03-01 00:00");
+    }
+}
@@ -0,0 +1,32 @@
+use bevy::app::PluginGroupBuilder;
+use bevy::log::LogPlugin;
+use bevy::prelude::*;
+use bevy::render::{settings::{RenderCreation, WgpuSettings}, RenderPlugin};
+use bevy::window::ExitCondition;
+use bevy::winit::WinitPlugin;
+
+// The default plugins with no window system and no GPU, so tests run anywhere.
+// Tests that click on things get a primary window to click into
+pub fn headless_plugins(primary_window: Option<Window>) -> PluginGroupBuilder {
+    DefaultPlugins
+        .set(RenderPlugin {
+            render_creation: RenderCreation::Automatic(WgpuSettings {
+                backends: None,
+                ..default()
+            }),
+            ..default()
+        })
+        .set(WindowPlugin {
+            primary_window,
+            exit_condition: ExitCondition::DontExit,
+            close_when_requested: false,
+        })
+        .disable::<WinitPlugin>()
+        .disable::<LogPlugin>()
+}
+
+// `App::run` normally does this, but the tests drive updates by hand
+pub fn finish(app: &mut App) {
+    app.finish();
+    app.cleanup();
+}
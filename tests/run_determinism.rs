@@ -0,0 +1,59 @@
+mod common;
+
+use bevy::prelude::*;
+
+use bubble_collapse::cli::LaunchOptions;
+use bubble_collapse::components::bubbles::{Bubble, BubbleType};
+use bubble_collapse::plugins::embedding::BubbleCollapsePlugins;
+use bubble_collapse::resources::bubbles::BubbleChances;
+
+// The whole game without a window, started straight into a run with the given
+// seed, like `--headless --start-in-game --seed` does
+fn seeded_app(seed: u64) -> App {
+    let mut app = App::new();
+    app
+        .add_plugins(common::headless_plugins(None))
+        .insert_resource(LaunchOptions {
+            seed: Some(seed),
+            start_in_game: true,
+            headless: true,
+            ..default()
+        })
+        .add_plugins(BubbleCollapsePlugins::default());
+    common::finish(&mut app);
+    app
+}
+
+fn spawned_types(seed: u64) -> Vec<BubbleType> {
+    let mut app = seeded_app(seed);
+    // startup, then entering the run
+    for _ in 0..2 {
+        app.update();
+    }
+    // every type gets a chance, so the pick depends on the order they're walked in
+    let mut chances = app.world_mut().resource_mut::<BubbleChances>();
+    for bubble_type in BubbleType::all() {
+        chances.set_chance(bubble_type, 20.0);
+    }
+    for _ in 0..300 {
+        app.update();
+    }
+
+    let mut bubbles = app.world_mut()
+        .query::<(Entity, &Bubble)>()
+        .iter(app.world())
+        .map(|(entity, bubble)| (entity, bubble.bubble_type))
+        .collect::<Vec<_>>();
+    bubbles.sort_by_key(|(entity, _)| *entity);
+    bubbles.into_iter().map(|(_, bubble_type)| bubble_type).collect()
+}
+
+#[test]
+fn same_seed_spawns_same_bubbles() {
+    let first = spawned_types(42);
+    let second = spawned_types(42);
+
+    assert!(first.len() > 10, "only {} bubbles spawned", first.len());
+    assert!(BubbleType::all().iter().filter(|bubble_type| first.contains(bubble_type)).count() > 1);
+    assert_eq!(first, second);
+}
@@ -0,0 +1,112 @@
+mod common;
+
+use bevy::input::{mouse::MouseButtonInput, ButtonState};
+use bevy::prelude::*;
+use bevy::window::{PrimaryWindow, WindowResolution};
+use bevy_egui::{egui, EguiContexts, EguiPlugin};
+
+use bubble_collapse::plugins::{
+    camera::CameraPlugin,
+    game_states::GameStatesPlugin,
+    input::InputPlugin,
+    interaction::InteractionPlugin,
+    pop_tools::PopToolsPlugin,
+};
+use bubble_collapse::resources::interaction::MouseClickEvent;
+
+// A headless app running the real input, UI focus and click handling systems
+fn headless_app() -> App {
+    let mut app = App::new();
+    app
+        .add_plugins(common::headless_plugins(Some(Window {
+            resolution: WindowResolution::new(1280.0, 720.0).with_scale_factor_override(1.0),
+            ..default()
+        })).disable::<bevy::audio::AudioPlugin>())
+        .add_plugins(EguiPlugin)
+        .add_plugins(GameStatesPlugin)
+        .add_plugins(CameraPlugin)
+        .add_plugins(InputPlugin)
+        .add_plugins(InteractionPlugin)
+        .add_plugins(PopToolsPlugin);
+    common::finish(&mut app);
+    app
+}
+
+fn spawn_button(app: &mut App) {
+    app.world_mut().spawn((
+        Button,
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(100.0),
+            top: Val::Px(100.0),
+            width: Val::Px(200.0),
+            height: Val::Px(100.0),
+            ..default()
+        },
+    ));
+}
+
+fn click_at(app: &mut App, position: Vec2) -> usize {
+    let window = app.world_mut()
+        .query_filtered::<Entity, With<PrimaryWindow>>()
+        .single(app.world());
+    app.world_mut().send_event(CursorMoved { window, position, delta: None });
+    app.world_mut().entity_mut(window).get_mut::<Window>().unwrap().set_cursor_position(Some(position));
+    // let the hover register before pressing, like a real pointer would
+    app.update();
+
+    app.world_mut().send_event(MouseButtonInput {
+        button: MouseButton::Left,
+        state: ButtonState::Pressed,
+        window,
+    });
+    app.update();
+
+    let events = app.world().resource::<Events<MouseClickEvent>>();
+    events.get_cursor().read(events).count()
+}
+
+fn settle(app: &mut App) {
+    // startup, camera setup and the first ui layout pass
+    for _ in 0..3 {
+        app.update();
+    }
+}
+
+#[test]
+fn click_on_playfield_reaches_gameplay() {
+    let mut app = headless_app();
+    spawn_button(&mut app);
+    settle(&mut app);
+
+    assert_eq!(click_at(&mut app, Vec2::new(800.0, 500.0)), 1);
+}
+
+#[test]
+fn click_on_ui_button_is_consumed() {
+    let mut app = headless_app();
+    spawn_button(&mut app);
+    settle(&mut app);
+
+    assert_eq!(click_at(&mut app, Vec2::new(200.0, 150.0)), 0);
+    let interaction = app.world_mut()
+        .query_filtered::<&Interaction, With<Button>>()
+        .single(app.world());
+    assert_eq!(*interaction, Interaction::Pressed);
+}
+
+#[test]
+fn click_on_egui_window_is_consumed() {
+    let mut app = headless_app();
+    app.add_systems(Update, |mut contexts: EguiContexts| {
+        egui::Window::new("Blocker")
+            .fixed_pos(egui::pos2(100.0, 100.0))
+            .fixed_size(egui::vec2(200.0, 100.0))
+            .show(contexts.ctx_mut(), |ui| {
+                ui.label("in the way");
+            });
+    });
+    settle(&mut app);
+
+    assert_eq!(click_at(&mut app, Vec2::new(200.0, 150.0)), 0);
+}